@@ -0,0 +1,407 @@
+use gl::types::*;
+use std::ffi::c_void;
+
+use super::{NativeBackend, RecordingBackend};
+
+//  Every gl function that goes through `gl_call!` has to be listed here. Each
+//  entry becomes a method of `GlBackend`, a variant of `GlCommand` and the
+//  matching `NativeBackend`/`RecordingBackend` implementations. The optional
+//  block after `=>` decides what the recording backend answers; without it the
+//  call returns `Default::default()`.
+macro_rules! gl_commands {
+    ($(
+        fn $name:ident($($arg:ident: $arg_type:ty),* $(,)?) $(-> $ret:ty)? $(=> |$recorder:ident| $record:block)?;
+    )*) => {
+        #[allow(non_snake_case, clippy::too_many_arguments, clippy::missing_safety_doc)]
+        pub trait GlBackend {
+            $(unsafe fn $name(&mut self, $($arg: $arg_type),*) $(-> $ret)?;)*
+        }
+
+        #[allow(unknown_lints, unpredictable_function_pointer_comparisons)]
+        #[derive(Clone, Debug, PartialEq)]
+        pub enum GlCommand {
+            $($name { $($arg: $arg_type),* },)*
+        }
+
+        //  `gl_call!` collects the arguments through `args` first, so they get
+        //  coerced to the parameter types and their temporaries live until the
+        //  call returns, and only then borrows the backend to `call` it.
+        #[doc(hidden)]
+        #[allow(
+            non_snake_case,
+            clippy::missing_safety_doc,
+            clippy::too_many_arguments,
+            clippy::unused_unit
+        )]
+        pub mod dispatch {
+            $(
+                pub mod $name {
+                    use super::super::*;
+
+                    #[inline]
+                    pub fn args($($arg: $arg_type),*) -> ($($arg_type,)*) {
+                        ($($arg,)*)
+                    }
+
+                    #[inline]
+                    pub unsafe fn call(
+                        backend: &mut dyn GlBackend,
+                        ($($arg,)*): ($($arg_type,)*),
+                    ) $(-> $ret)? {
+                        backend.$name($($arg),*)
+                    }
+                }
+            )*
+        }
+
+        #[allow(non_snake_case)]
+        impl GlBackend for NativeBackend {
+            $(
+                #[inline]
+                unsafe fn $name(&mut self, $($arg: $arg_type),*) $(-> $ret)? {
+                    let result = gl::$name($($arg),*);
+                    self.check_errors(stringify!($name));
+
+                    result
+                }
+            )*
+        }
+
+        #[allow(non_snake_case, unused_variables)]
+        impl GlBackend for RecordingBackend {
+            $(
+                unsafe fn $name(&mut self, $($arg: $arg_type),*) $(-> $ret)? {
+                    self.push(GlCommand::$name { $($arg),* });
+
+                    gl_commands!(@respond self $(, |$recorder| $record)?)
+                }
+            )*
+        }
+    };
+
+    (@respond $this:tt) => {
+        Default::default()
+    };
+    (@respond $this:tt, |$recorder:ident| $record:block) => {{
+        let $recorder = $this;
+        $record
+    }};
+}
+
+gl_commands! {
+    fn ActiveTexture(texture: GLenum);
+    fn AttachShader(program: GLuint, shader: GLuint);
+    fn BindBuffer(target: GLenum, buffer: GLuint);
+    fn BindBufferBase(target: GLenum, index: GLuint, buffer: GLuint);
+    fn BindTexture(target: GLenum, texture: GLuint);
+    fn BindVertexArray(array: GLuint);
+    fn BlendFunc(sfactor: GLenum, dfactor: GLenum);
+    fn BufferData(target: GLenum, size: GLsizeiptr, data: *const c_void, usage: GLenum);
+    fn BufferSubData(target: GLenum, offset: GLintptr, size: GLsizeiptr, data: *const c_void);
+    fn Clear(mask: GLbitfield);
+    fn ClearColor(red: GLfloat, green: GLfloat, blue: GLfloat, alpha: GLfloat);
+    fn ClientWaitSync(sync: GLsync, flags: GLbitfield, timeout: GLuint64) -> GLenum => |recorder| {
+        gl::ALREADY_SIGNALED
+    };
+    fn CompileShader(shader: GLuint);
+    fn CreateBuffers(n: GLsizei, buffers: *mut GLuint) => |recorder| {
+        recorder.write_names(n, buffers)
+    };
+    fn CreateProgram() -> GLuint => |recorder| {
+        recorder.next_name()
+    };
+    fn CreateShader(type_: GLenum) -> GLuint => |recorder| {
+        recorder.next_name()
+    };
+    fn CreateVertexArrays(n: GLsizei, arrays: *mut GLuint) => |recorder| {
+        recorder.write_names(n, arrays)
+    };
+    fn CullFace(mode: GLenum);
+    fn DebugMessageCallback(callback: GLDEBUGPROC, user_param: *const c_void);
+    fn DeleteBuffers(n: GLsizei, buffers: *const GLuint) => |recorder| {
+        recorder.replace_last(GlCommand::DeleteBuffers { n, buffers: RecordingBackend::keep_names(n, buffers) })
+    };
+    fn DeleteProgram(program: GLuint);
+    fn DeleteShader(shader: GLuint);
+    fn DeleteSync(sync: GLsync);
+    fn DeleteTextures(n: GLsizei, textures: *const GLuint) => |recorder| {
+        recorder.replace_last(GlCommand::DeleteTextures { n, textures: RecordingBackend::keep_names(n, textures) })
+    };
+    fn DeleteVertexArrays(n: GLsizei, arrays: *const GLuint) => |recorder| {
+        recorder.replace_last(GlCommand::DeleteVertexArrays { n, arrays: RecordingBackend::keep_names(n, arrays) })
+    };
+    fn DepthFunc(func: GLenum);
+    fn DetachShader(program: GLuint, shader: GLuint);
+    fn Disable(cap: GLenum);
+    fn DispatchCompute(num_groups_x: GLuint, num_groups_y: GLuint, num_groups_z: GLuint);
+    fn DrawElements(mode: GLenum, count: GLsizei, type_: GLenum, indices: *const c_void);
+    fn DrawElementsInstanced(
+        mode: GLenum,
+        count: GLsizei,
+        type_: GLenum,
+        indices: *const c_void,
+        instancecount: GLsizei,
+    );
+    fn DrawElementsInstancedBaseInstance(
+        mode: GLenum,
+        count: GLsizei,
+        type_: GLenum,
+        indices: *const c_void,
+        instancecount: GLsizei,
+        baseinstance: GLuint,
+    );
+    fn Enable(cap: GLenum);
+    fn EnableVertexArrayAttrib(vaobj: GLuint, index: GLuint);
+    fn EnableVertexAttribArray(index: GLuint);
+    fn FenceSync(condition: GLenum, flags: GLbitfield) -> GLsync => |recorder| {
+        std::ptr::null()
+    };
+    fn FlushMappedNamedBufferRange(buffer: GLuint, offset: GLintptr, length: GLsizeiptr);
+    fn FrontFace(mode: GLenum);
+    fn GenTextures(n: GLsizei, textures: *mut GLuint) => |recorder| {
+        recorder.write_names(n, textures)
+    };
+    fn GenerateMipmap(target: GLenum);
+    fn GetActiveAttrib(
+        program: GLuint,
+        index: GLuint,
+        buf_size: GLsizei,
+        length: *mut GLsizei,
+        size: *mut GLint,
+        type_: *mut GLenum,
+        name: *mut GLchar,
+    );
+    fn GetActiveUniform(
+        program: GLuint,
+        index: GLuint,
+        buf_size: GLsizei,
+        length: *mut GLsizei,
+        size: *mut GLint,
+        type_: *mut GLenum,
+        name: *mut GLchar,
+    );
+    fn GetAttribLocation(program: GLuint, name: *const GLchar) -> GLint;
+    fn GetIntegerv(pname: GLenum, data: *mut GLint);
+    fn GetNamedBufferParameteriv(buffer: GLuint, pname: GLenum, params: *mut GLint);
+    fn GetProgramBinary(
+        program: GLuint,
+        buf_size: GLsizei,
+        length: *mut GLsizei,
+        binary_format: *mut GLenum,
+        binary: *mut c_void,
+    );
+    fn GetProgramInfoLog(
+        program: GLuint,
+        buf_size: GLsizei,
+        length: *mut GLsizei,
+        info_log: *mut GLchar,
+    );
+    fn GetProgramiv(program: GLuint, pname: GLenum, params: *mut GLint) => |recorder| {
+        recorder.write_status(pname, params)
+    };
+    fn GetShaderInfoLog(
+        shader: GLuint,
+        buf_size: GLsizei,
+        length: *mut GLsizei,
+        info_log: *mut GLchar,
+    );
+    fn GetShaderiv(shader: GLuint, pname: GLenum, params: *mut GLint) => |recorder| {
+        recorder.write_status(pname, params)
+    };
+    fn GetString(name: GLenum) -> *const GLubyte => |recorder| {
+        b"RecordingBackend\0".as_ptr()
+    };
+    fn GetStringi(name: GLenum, index: GLuint) -> *const GLubyte => |recorder| {
+        std::ptr::null()
+    };
+    fn GetUniformBlockIndex(program: GLuint, uniform_block_name: *const GLchar) -> GLuint;
+    fn GetUniformLocation(program: GLuint, name: *const GLchar) -> GLint;
+    fn GetUniformfv(program: GLuint, location: GLint, params: *mut GLfloat);
+    fn GetUniformiv(program: GLuint, location: GLint, params: *mut GLint);
+    fn GetUniformuiv(program: GLuint, location: GLint, params: *mut GLuint);
+    fn LinkProgram(program: GLuint);
+    fn MapNamedBufferRange(
+        buffer: GLuint,
+        offset: GLintptr,
+        length: GLsizeiptr,
+        access: GLbitfield,
+    ) -> *mut c_void => |recorder| {
+        std::ptr::null_mut()
+    };
+    fn MemoryBarrier(barriers: GLbitfield);
+    fn MemoryBarrierByRegion(barriers: GLbitfield);
+    fn NamedBufferStorage(
+        buffer: GLuint,
+        size: GLsizeiptr,
+        data: *const c_void,
+        flags: GLbitfield,
+    );
+    fn NamedBufferSubData(
+        buffer: GLuint,
+        offset: GLintptr,
+        size: GLsizeiptr,
+        data: *const c_void,
+    );
+    fn ObjectLabel(identifier: GLenum, name: GLuint, length: GLsizei, label: *const GLchar);
+    fn PatchParameteri(pname: GLenum, value: GLint);
+    fn PolygonMode(face: GLenum, mode: GLenum);
+    fn ProgramBinary(
+        program: GLuint,
+        binary_format: GLenum,
+        binary: *const c_void,
+        length: GLsizei,
+    );
+    fn ProgramParameteri(program: GLuint, pname: GLenum, value: GLint);
+    fn ShaderSource(
+        shader: GLuint,
+        count: GLsizei,
+        string: *const *const GLchar,
+        length: *const GLint,
+    );
+    fn TexImage2D(
+        target: GLenum,
+        level: GLint,
+        internalformat: GLint,
+        width: GLsizei,
+        height: GLsizei,
+        border: GLint,
+        format: GLenum,
+        type_: GLenum,
+        pixels: *const c_void,
+    );
+    fn TexParameteri(target: GLenum, pname: GLenum, param: GLint);
+    fn Uniform1f(location: GLint, v0: GLfloat);
+    fn Uniform1fv(location: GLint, count: GLsizei, value: *const GLfloat);
+    fn Uniform1i(location: GLint, v0: GLint);
+    fn Uniform1iv(location: GLint, count: GLsizei, value: *const GLint);
+    fn Uniform1uiv(location: GLint, count: GLsizei, value: *const GLuint);
+    fn Uniform2fv(location: GLint, count: GLsizei, value: *const GLfloat);
+    fn Uniform2iv(location: GLint, count: GLsizei, value: *const GLint);
+    fn Uniform2uiv(location: GLint, count: GLsizei, value: *const GLuint);
+    fn Uniform3f(location: GLint, v0: GLfloat, v1: GLfloat, v2: GLfloat);
+    fn Uniform3fv(location: GLint, count: GLsizei, value: *const GLfloat);
+    fn Uniform3iv(location: GLint, count: GLsizei, value: *const GLint);
+    fn Uniform3uiv(location: GLint, count: GLsizei, value: *const GLuint);
+    fn Uniform4fv(location: GLint, count: GLsizei, value: *const GLfloat);
+    fn Uniform4iv(location: GLint, count: GLsizei, value: *const GLint);
+    fn Uniform4uiv(location: GLint, count: GLsizei, value: *const GLuint);
+    fn UniformBlockBinding(
+        program: GLuint,
+        uniform_block_index: GLuint,
+        uniform_block_binding: GLuint,
+    );
+    fn UniformMatrix2fv(
+        location: GLint,
+        count: GLsizei,
+        transpose: GLboolean,
+        value: *const GLfloat,
+    );
+    fn UniformMatrix2x3fv(
+        location: GLint,
+        count: GLsizei,
+        transpose: GLboolean,
+        value: *const GLfloat,
+    );
+    fn UniformMatrix2x4fv(
+        location: GLint,
+        count: GLsizei,
+        transpose: GLboolean,
+        value: *const GLfloat,
+    );
+    fn UniformMatrix3fv(
+        location: GLint,
+        count: GLsizei,
+        transpose: GLboolean,
+        value: *const GLfloat,
+    );
+    fn UniformMatrix3x2fv(
+        location: GLint,
+        count: GLsizei,
+        transpose: GLboolean,
+        value: *const GLfloat,
+    );
+    fn UniformMatrix3x4fv(
+        location: GLint,
+        count: GLsizei,
+        transpose: GLboolean,
+        value: *const GLfloat,
+    );
+    fn UniformMatrix4fv(
+        location: GLint,
+        count: GLsizei,
+        transpose: GLboolean,
+        value: *const GLfloat,
+    );
+    fn UniformMatrix4x2fv(
+        location: GLint,
+        count: GLsizei,
+        transpose: GLboolean,
+        value: *const GLfloat,
+    );
+    fn UniformMatrix4x3fv(
+        location: GLint,
+        count: GLsizei,
+        transpose: GLboolean,
+        value: *const GLfloat,
+    );
+    fn UnmapNamedBuffer(buffer: GLuint) -> GLboolean => |recorder| {
+        gl::TRUE
+    };
+    fn UseProgram(program: GLuint);
+    fn VertexArrayAttribBinding(vaobj: GLuint, attribindex: GLuint, bindingindex: GLuint);
+    fn VertexArrayAttribFormat(
+        vaobj: GLuint,
+        attribindex: GLuint,
+        size: GLint,
+        type_: GLenum,
+        normalized: GLboolean,
+        relativeoffset: GLuint,
+    );
+    fn VertexArrayAttribIFormat(
+        vaobj: GLuint,
+        attribindex: GLuint,
+        size: GLint,
+        type_: GLenum,
+        relativeoffset: GLuint,
+    );
+    fn VertexArrayAttribLFormat(
+        vaobj: GLuint,
+        attribindex: GLuint,
+        size: GLint,
+        type_: GLenum,
+        relativeoffset: GLuint,
+    );
+    fn VertexArrayBindingDivisor(vaobj: GLuint, bindingindex: GLuint, divisor: GLuint);
+    fn VertexArrayElementBuffer(vaobj: GLuint, buffer: GLuint);
+    fn VertexArrayVertexBuffer(
+        vaobj: GLuint,
+        bindingindex: GLuint,
+        buffer: GLuint,
+        offset: GLintptr,
+        stride: GLsizei,
+    );
+    fn VertexAttribDivisor(index: GLuint, divisor: GLuint);
+    fn VertexAttribIPointer(
+        index: GLuint,
+        size: GLint,
+        type_: GLenum,
+        stride: GLsizei,
+        pointer: *const c_void,
+    );
+    fn VertexAttribLPointer(
+        index: GLuint,
+        size: GLint,
+        type_: GLenum,
+        stride: GLsizei,
+        pointer: *const c_void,
+    );
+    fn VertexAttribPointer(
+        index: GLuint,
+        size: GLint,
+        type_: GLenum,
+        normalized: GLboolean,
+        stride: GLsizei,
+        pointer: *const c_void,
+    );
+    fn Viewport(x: GLint, y: GLint, width: GLsizei, height: GLsizei);
+}
@@ -0,0 +1,28 @@
+mod commands;
+mod native;
+mod recording;
+
+pub use commands::{dispatch, GlBackend, GlCommand};
+pub use native::NativeBackend;
+pub use recording::{record, RecordingBackend};
+
+use std::cell::RefCell;
+
+thread_local! {
+    static BACKEND: RefCell<Box<dyn GlBackend>> = RefCell::new(Box::new(NativeBackend));
+}
+
+/// Replaces the backend used by `gl_call!` on the current thread and returns
+/// the previous one.
+pub fn set_backend(backend: Box<dyn GlBackend>) -> Box<dyn GlBackend> {
+    BACKEND.with(|current| current.replace(backend))
+}
+
+#[doc(hidden)]
+#[inline]
+pub fn with<F, R>(f: F) -> R
+where
+    F: FnOnce(&mut dyn GlBackend) -> R,
+{
+    BACKEND.with(|backend| f(backend.borrow_mut().as_mut()))
+}
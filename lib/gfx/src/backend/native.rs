@@ -0,0 +1,15 @@
+use crate::basic::get_gl_error_string;
+
+/// Forwards every command to the loaded gl function pointers and logs
+/// whatever `glGetError` reports afterwards.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct NativeBackend;
+
+impl NativeBackend {
+    #[inline]
+    pub(super) fn check_errors(&self, command: &str) {
+        while let Some(message) = get_gl_error_string(unsafe { gl::GetError() }) {
+            log::warn!("{} (in gl{})", message, command);
+        }
+    }
+}
@@ -0,0 +1,91 @@
+use gl::types::*;
+use std::{cell::RefCell, rc::Rc, slice};
+
+use super::{set_backend, GlBackend, GlCommand};
+
+/// Stores every command instead of executing it, so code built on `gl_call!`
+/// can run without a context. Object names are handed out incrementally and
+/// compile/link status queries always succeed; every other query returns
+/// zero. The name arrays of `glDelete*` commands are copied, so they can be
+/// read from the recorded commands.
+#[derive(Clone, Debug, Default)]
+pub struct RecordingBackend {
+    commands: Rc<RefCell<Vec<GlCommand>>>,
+    last_name: GLuint,
+}
+
+impl RecordingBackend {
+    pub fn new() -> RecordingBackend {
+        RecordingBackend::default()
+    }
+
+    /// The recorded commands. The log is shared, so it can still be read after
+    /// the backend itself has been handed to `set_backend`.
+    pub fn commands(&self) -> Rc<RefCell<Vec<GlCommand>>> {
+        self.commands.clone()
+    }
+
+    pub(super) fn push(&mut self, command: GlCommand) {
+        self.commands.borrow_mut().push(command);
+    }
+
+    pub(super) fn next_name(&mut self) -> GLuint {
+        self.last_name += 1;
+        self.last_name
+    }
+
+    pub(super) unsafe fn write_names(&mut self, count: GLsizei, names: *mut GLuint) {
+        for index in 0..count.max(0) as usize {
+            *names.add(index) = self.next_name();
+        }
+    }
+
+    //  Swaps the command just pushed for `command`.
+    pub(super) fn replace_last(&mut self, command: GlCommand) {
+        if let Some(last) = self.commands.borrow_mut().last_mut() {
+            *last = command;
+        }
+    }
+
+    //  A copy of the names passed to a delete, which usually live on the
+    //  caller's stack, so the recorded command can still be read afterwards.
+    //  The copy is leaked; the recorder only runs in tests.
+    pub(super) unsafe fn keep_names(count: GLsizei, names: *const GLuint) -> *const GLuint {
+        let names: Box<[GLuint]> = slice::from_raw_parts(names, count.max(0) as usize).into();
+        Box::leak(names).as_ptr()
+    }
+
+    pub(super) unsafe fn write_status(&mut self, pname: GLenum, params: *mut GLint) {
+        *params = match pname {
+            gl::COMPILE_STATUS | gl::LINK_STATUS | gl::VALIDATE_STATUS => gl::TRUE as GLint,
+            _ => 0,
+        };
+    }
+}
+
+/// Runs `f` with a fresh `RecordingBackend` installed on this thread and
+/// returns the commands it issued. The previous backend is restored
+/// afterwards, even if `f` panics.
+pub fn record<F>(f: F) -> Vec<GlCommand>
+where
+    F: FnOnce(),
+{
+    struct Restore(Option<Box<dyn GlBackend>>);
+
+    impl Drop for Restore {
+        fn drop(&mut self) {
+            if let Some(backend) = self.0.take() {
+                set_backend(backend);
+            }
+        }
+    }
+
+    let recorder = RecordingBackend::new();
+    let commands = recorder.commands();
+
+    let restore = Restore(Some(set_backend(Box::new(recorder))));
+    f();
+    drop(restore);
+
+    commands.take()
+}
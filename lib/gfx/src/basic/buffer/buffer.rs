@@ -0,0 +1,273 @@
+use gl::types::*;
+use super::{map_buffer_range, BufferMapping, MapAccess, Pod};
+use crate::{
+    basic::{object_label, track_object},
+    delete_object,
+    prelude::{gl_call, Bindable, Labelable},
+    GlContext,
+};
+use std::{ffi::c_void, marker::PhantomData, mem, ptr};
+
+/// The binding point a `Buffer` is bound to.
+pub trait BufferTarget {
+    const TARGET: GLenum;
+}
+
+/// A target with indexed binding points (`glBindBufferBase`).
+pub trait IndexedBufferTarget: BufferTarget {}
+
+macro_rules! buffer_target {
+    ($($(#[$meta:meta])* $target:ident => $gl_target:path),+ $(,)?) => {
+        $(
+            $(#[$meta])*
+            #[derive(Clone, Copy, Debug)]
+            pub struct $target;
+
+            impl BufferTarget for $target {
+                const TARGET: GLenum = $gl_target;
+            }
+        )+
+    };
+}
+
+buffer_target!(
+    /// `GL_ARRAY_BUFFER`, vertex attributes.
+    ArrayTarget => gl::ARRAY_BUFFER,
+    /// `GL_ELEMENT_ARRAY_BUFFER`, indices.
+    ElementArrayTarget => gl::ELEMENT_ARRAY_BUFFER,
+    /// `GL_UNIFORM_BUFFER`, uniform blocks.
+    UniformTarget => gl::UNIFORM_BUFFER,
+    /// `GL_SHADER_STORAGE_BUFFER`, shader storage blocks.
+    ShaderStorageTarget => gl::SHADER_STORAGE_BUFFER,
+    /// `GL_DRAW_INDIRECT_BUFFER`, draw commands.
+    DrawIndirectTarget => gl::DRAW_INDIRECT_BUFFER,
+);
+
+impl IndexedBufferTarget for UniformTarget {}
+impl IndexedBufferTarget for ShaderStorageTarget {}
+
+/// How often the content of a buffer is expected to change and who reads it,
+/// a hint for the driver.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum BufferUsage {
+    StreamDraw,
+    StreamRead,
+    StreamCopy,
+    StaticDraw,
+    StaticRead,
+    StaticCopy,
+    DynamicDraw,
+    DynamicRead,
+    DynamicCopy,
+}
+
+impl BufferUsage {
+    pub fn gl_type(&self) -> GLenum {
+        match self {
+            BufferUsage::StreamDraw => gl::STREAM_DRAW,
+            BufferUsage::StreamRead => gl::STREAM_READ,
+            BufferUsage::StreamCopy => gl::STREAM_COPY,
+            BufferUsage::StaticDraw => gl::STATIC_DRAW,
+            BufferUsage::StaticRead => gl::STATIC_READ,
+            BufferUsage::StaticCopy => gl::STATIC_COPY,
+            BufferUsage::DynamicDraw => gl::DYNAMIC_DRAW,
+            BufferUsage::DynamicRead => gl::DYNAMIC_READ,
+            BufferUsage::DynamicCopy => gl::DYNAMIC_COPY,
+        }
+    }
+}
+
+/// A buffer object holding elements of type `T`, bound to target `K`. It
+/// remembers how many elements were written (`len`) and how many fit into its
+/// storage (`capacity`).
+pub struct Buffer<T: Pod, K: BufferTarget> {
+    buffer_id: GLuint,
+    len: usize,
+    capacity: usize,
+    _marker: PhantomData<(T, K)>,
+}
+
+impl<T: Pod, K: BufferTarget> Buffer<T, K> {
+    pub fn new(_context: &GlContext) -> Buffer<T, K> {
+        let mut buffer_id = 0;
+        gl_call!(gl::CreateBuffers(1, &mut buffer_id));
+        track_object(gl::BUFFER, buffer_id);
+
+        Buffer {
+            buffer_id,
+            len: 0,
+            capacity: 0,
+            _marker: PhantomData,
+        }
+    }
+
+    pub fn with_data(context: &GlContext, data: &[T], usage: BufferUsage) -> Buffer<T, K> {
+        let mut buffer = Buffer::new(context);
+        buffer.add_data(data, usage);
+
+        buffer
+    }
+
+    pub(crate) fn id(&self) -> GLuint {
+        self.buffer_id
+    }
+
+    //  The same buffer object with its content seen as bytes, for owners that
+    //  only know the element type at runtime.
+    pub(crate) fn into_bytes(self) -> Buffer<u8, K> {
+        let buffer = mem::ManuallyDrop::new(self);
+
+        Buffer {
+            buffer_id: buffer.buffer_id,
+            len: buffer.len * mem::size_of::<T>(),
+            capacity: buffer.size(),
+            _marker: PhantomData,
+        }
+    }
+
+    /// Reallocates the buffer to hold exactly `data`.
+    pub fn add_data(&mut self, data: &[T], usage: BufferUsage) {
+        self.buffer_data(data.len(), data.as_ptr() as *const c_void, usage);
+        self.len = data.len();
+    }
+
+    /// Reallocates the buffer with room for `capacity` elements of undefined
+    /// content. Also orphans the old storage, so the driver does not have to
+    /// wait for draws still reading it.
+    pub fn allocate(&mut self, capacity: usize, usage: BufferUsage) {
+        self.buffer_data(capacity, ptr::null(), usage);
+        self.len = 0;
+    }
+
+    /// Overwrites `data.len()` elements starting at element `offset`, without
+    /// reallocating the buffer. Panics if they do not fit into the capacity.
+    pub fn update_range(&mut self, offset: usize, data: &[T]) {
+        let end = offset + data.len();
+        assert!(
+            end <= self.capacity,
+            "elements {}..{} are out of the buffer's capacity of {}",
+            offset,
+            end,
+            self.capacity
+        );
+
+        gl_call!(gl::NamedBufferSubData(
+            self.buffer_id,
+            (offset * mem::size_of::<T>()) as GLintptr,
+            mem::size_of_val(data) as GLsizeiptr,
+            data.as_ptr() as *const c_void
+        ));
+        self.len = self.len.max(end);
+    }
+
+    /// Creates immutable storage (`glBufferStorage`) holding `data`. Its size
+    /// can not change afterwards, so `add_data` and `allocate` must not be
+    /// called anymore. `flags` are `GL_DYNAMIC_STORAGE_BIT`, `GL_MAP_*_BIT`
+    /// and so on.
+    pub fn storage(&mut self, data: &[T], flags: GLbitfield) {
+        self.buffer_storage(data.len(), data.as_ptr() as *const c_void, flags);
+        self.len = data.len();
+    }
+
+    /// Like `storage`, with room for `capacity` elements of undefined content.
+    pub fn allocate_storage(&mut self, capacity: usize, flags: GLbitfield) {
+        self.buffer_storage(capacity, ptr::null(), flags);
+        self.len = 0;
+    }
+
+    /// The number of elements written since the buffer was last allocated.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// The number of elements the storage has room for.
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// The size of the storage in bytes.
+    pub fn size(&self) -> usize {
+        self.capacity * mem::size_of::<T>()
+    }
+
+    /// Maps the whole storage, see `map_range`.
+    pub fn map<A: MapAccess>(&self, flags: GLbitfield) -> Option<BufferMapping<'_, T, A>> {
+        self.map_range(0, self.capacity, flags)
+    }
+
+    /// Maps `len` elements starting at element `offset`, for reading and/or
+    /// writing depending on `A` (`MapRead`, `MapWrite`, `MapReadWrite`).
+    /// `flags` takes the other `GL_MAP_*_BIT` flags of `glMapBufferRange`.
+    /// Returns `None` if the range is out of the capacity or the driver
+    /// refused to map it.
+    pub fn map_range<A: MapAccess>(
+        &self,
+        offset: usize,
+        len: usize,
+        flags: GLbitfield,
+    ) -> Option<BufferMapping<'_, T, A>> {
+        if offset + len > self.capacity {
+            return None;
+        }
+
+        map_buffer_range(self.buffer_id, offset, len, flags)
+    }
+
+    //  Leaves the buffer bound, filling an `Ebo` while a vao is bound attaches
+    //  it to the vao.
+    fn buffer_data(&mut self, capacity: usize, data: *const c_void, usage: BufferUsage) {
+        self.bind();
+        gl_call!(gl::BufferData(
+            K::TARGET,
+            (capacity * mem::size_of::<T>()) as GLsizeiptr,
+            data,
+            usage.gl_type()
+        ));
+        self.capacity = capacity;
+    }
+
+    fn buffer_storage(&mut self, capacity: usize, data: *const c_void, flags: GLbitfield) {
+        gl_call!(gl::NamedBufferStorage(
+            self.buffer_id,
+            (capacity * mem::size_of::<T>()) as GLsizeiptr,
+            data,
+            flags
+        ));
+        self.capacity = capacity;
+    }
+}
+
+impl<T: Pod, K: IndexedBufferTarget> Buffer<T, K> {
+    /// Binds the buffer to the indexed binding point `binding` of its target.
+    pub fn bind_base(&self, binding: GLuint) {
+        gl_call!(gl::BindBufferBase(K::TARGET, binding, self.buffer_id));
+    }
+}
+
+impl<T: Pod, K: BufferTarget> Bindable for Buffer<T, K> {
+    #[inline]
+    fn bind(&self) {
+        gl_call!(gl::BindBuffer(K::TARGET, self.buffer_id));
+    }
+
+    #[inline]
+    fn unbind(&self) {
+        gl_call!(gl::BindBuffer(K::TARGET, 0));
+    }
+}
+
+impl<T: Pod, K: BufferTarget> Labelable for Buffer<T, K> {
+    fn label(&self, label: &str) {
+        object_label(gl::BUFFER, self.buffer_id, label);
+    }
+}
+
+impl<T: Pod, K: BufferTarget> Drop for Buffer<T, K> {
+    fn drop(&mut self) {
+        delete_object(gl::BUFFER, self.buffer_id);
+    }
+}
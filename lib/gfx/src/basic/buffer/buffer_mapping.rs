@@ -0,0 +1,143 @@
+use gl::types::*;
+use super::Pod;
+use crate::gl_call;
+use std::{
+    marker::PhantomData,
+    mem,
+    ops::{Deref, DerefMut},
+    ptr, slice,
+};
+
+/// How a range is mapped, decides what its `BufferMapping` allows.
+pub trait MapAccess {
+    /// The `GL_MAP_READ_BIT`/`GL_MAP_WRITE_BIT` part of the access flags.
+    const BITS: GLbitfield;
+}
+
+/// Can be read, `GL_MAP_READ_BIT`.
+pub enum MapRead {}
+
+/// Can only be written with `BufferMapping::write`, `GL_MAP_WRITE_BIT`.
+/// Reading a write-only mapping is undefined, so it does not deref to a
+/// slice.
+pub enum MapWrite {}
+
+/// Can be read and written through the slice, both bits.
+pub enum MapReadWrite {}
+
+impl MapAccess for MapRead {
+    const BITS: GLbitfield = gl::MAP_READ_BIT;
+}
+
+impl MapAccess for MapWrite {
+    const BITS: GLbitfield = gl::MAP_WRITE_BIT;
+}
+
+impl MapAccess for MapReadWrite {
+    const BITS: GLbitfield = gl::MAP_READ_BIT | gl::MAP_WRITE_BIT;
+}
+
+/// A mapped range of a buffer. Ranges mapped for reading deref to a slice of
+/// `T`, the ones mapped for writing are written with `write` (or through the
+/// slice for `MapReadWrite`). The buffer is unmapped when the guard is
+/// dropped.
+pub struct BufferMapping<'a, T: Pod, A: MapAccess> {
+    buffer_id: GLuint,
+    data: *mut T,
+    len: usize,
+    _marker: PhantomData<(&'a mut [T], A)>,
+}
+
+/// Maps `len` elements starting at element `offset`. The read and write bits
+/// come from `A`, `flags` adds the other `GL_MAP_*_BIT`s. Returns `None` if
+/// the driver refused, e.g. because the range is out of bounds.
+pub(crate) fn map_buffer_range<'a, T: Pod, A: MapAccess>(
+    buffer_id: GLuint,
+    offset: usize,
+    len: usize,
+    flags: GLbitfield,
+) -> Option<BufferMapping<'a, T, A>> {
+    let access = (flags & !(gl::MAP_READ_BIT | gl::MAP_WRITE_BIT)) | A::BITS;
+    let data;
+    gl_call!(data = gl::MapNamedBufferRange(
+        buffer_id,
+        (offset * mem::size_of::<T>()) as GLintptr,
+        (len * mem::size_of::<T>()) as GLsizeiptr,
+        access
+    ));
+    if data.is_null() {
+        return None;
+    }
+
+    Some(BufferMapping {
+        buffer_id,
+        data: data as *mut T,
+        len,
+        _marker: PhantomData,
+    })
+}
+
+impl<T: Pod, A: MapAccess> BufferMapping<'_, T, A> {
+    /// The number of mapped elements.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+}
+
+impl<T: Pod> BufferMapping<'_, T, MapWrite> {
+    /// Copies `data` into the mapping, starting at element `offset`. Panics
+    /// if it does not fit.
+    pub fn write(&mut self, offset: usize, data: &[T]) {
+        let end = offset + data.len();
+        assert!(end <= self.len, "elements {}..{} are out of the mapping of {}", offset, end, self.len);
+
+        //  Without forming a slice of the mapped memory, it must not be read.
+        unsafe { ptr::copy_nonoverlapping(data.as_ptr(), self.data.add(offset), data.len()) };
+    }
+}
+
+impl<T: Pod> BufferMapping<'_, T, MapReadWrite> {
+    /// Copies `data` into the mapping, starting at element `offset`. Panics
+    /// if it does not fit.
+    pub fn write(&mut self, offset: usize, data: &[T]) {
+        self[offset..offset + data.len()].copy_from_slice(data);
+    }
+}
+
+impl<T: Pod> Deref for BufferMapping<'_, T, MapRead> {
+    type Target = [T];
+
+    fn deref(&self) -> &[T] {
+        unsafe { slice::from_raw_parts(self.data, self.len) }
+    }
+}
+
+impl<T: Pod> Deref for BufferMapping<'_, T, MapReadWrite> {
+    type Target = [T];
+
+    fn deref(&self) -> &[T] {
+        unsafe { slice::from_raw_parts(self.data, self.len) }
+    }
+}
+
+impl<T: Pod> DerefMut for BufferMapping<'_, T, MapReadWrite> {
+    fn deref_mut(&mut self) -> &mut [T] {
+        unsafe { slice::from_raw_parts_mut(self.data, self.len) }
+    }
+}
+
+impl<T: Pod, A: MapAccess> Drop for BufferMapping<'_, T, A> {
+    fn drop(&mut self) {
+        let intact;
+        gl_call!(intact = gl::UnmapNamedBuffer(self.buffer_id));
+        if intact == gl::FALSE {
+            //  The data store got corrupted while mapped (e.g. a mode switch),
+            //  its content is undefined now.
+            log::warn!("Buffer {} was corrupted while mapped", self.buffer_id);
+        }
+    }
+}
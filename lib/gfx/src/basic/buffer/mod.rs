@@ -0,0 +1,13 @@
+mod buffer;
+mod buffer_mapping;
+mod pod;
+mod ring_buffer;
+
+pub use buffer::{
+    ArrayTarget, Buffer, BufferTarget, BufferUsage, DrawIndirectTarget, ElementArrayTarget,
+    IndexedBufferTarget, ShaderStorageTarget, UniformTarget,
+};
+pub use buffer_mapping::{BufferMapping, MapAccess, MapRead, MapReadWrite, MapWrite};
+pub(crate) use buffer_mapping::map_buffer_range;
+pub use pod::{cast_slice, Pod};
+pub use ring_buffer::RingBuffer;
@@ -0,0 +1,38 @@
+use cgmath::{Matrix2, Matrix3, Matrix4, Point2, Point3, Quaternion, Vector2, Vector3, Vector4};
+use std::{mem, slice};
+
+/// Plain old data: a `Copy` type without padding, pointers or invalid bit
+/// patterns, so a slice of it can be uploaded to and read back from a buffer
+/// as raw bytes. Implement it for `#[repr(C)]` structs with `#[derive(Pod)]`,
+/// which checks both requirements at compile time.
+///
+/// # Safety
+///
+/// Every byte of the type has to be initialized and every bit pattern has to
+/// be a valid value.
+pub unsafe trait Pod: Copy + 'static {}
+
+/// The bytes of a slice of plain old data.
+pub fn cast_slice<T: Pod>(data: &[T]) -> &[u8] {
+    unsafe { slice::from_raw_parts(data.as_ptr() as *const u8, mem::size_of_val(data)) }
+}
+
+macro_rules! pod {
+    ($($pod:ty),+ $(,)?) => {
+        $(unsafe impl Pod for $pod {})+
+    };
+}
+
+pod!(u8, i8, u16, i16, u32, i32, u64, i64, f32, f64);
+
+unsafe impl<T: Pod, const N: usize> Pod for [T; N] {}
+
+//  cgmath types are `repr(C)` structs of a single component type, so they have
+//  no padding either.
+macro_rules! pod_generic {
+    ($($pod:ident),+ $(,)?) => {
+        $(unsafe impl<T: Pod> Pod for $pod<T> {})+
+    };
+}
+
+pod_generic!(Vector2, Vector3, Vector4, Point2, Point3, Matrix2, Matrix3, Matrix4, Quaternion);
@@ -0,0 +1,194 @@
+use gl::types::*;
+use super::{map_buffer_range, BufferMapping, MapWrite, Pod};
+use crate::{
+    basic::{BufferUsage, Vbo},
+    context::supports,
+    gl_call, GlContext,
+};
+use std::{mem, ptr};
+
+const STORAGE_FLAGS: GLbitfield = gl::MAP_WRITE_BIT | gl::MAP_PERSISTENT_BIT | gl::MAP_COHERENT_BIT;
+
+/// A persistently mapped `Vbo` for data rewritten every frame, like debug
+/// lines or UI vertices. It is split into `sections` of `capacity` elements;
+/// every `write` goes to the next section, after waiting for the GPU to finish
+/// the draws that were fenced on it with `fence`.
+///
+/// Persistent mappings need OpenGL 4.4 or ARB_buffer_storage. Without them
+/// (or when the driver refuses the mapping) the sections live in a plain
+/// `Vbo` that `write` updates with `glBufferSubData`, which works the same
+/// but copies the data once more.
+///
+/// ```ignore
+/// let first = ring.write(&vertices);
+/// vao.bind_vertex_buffer(0, ring.vbo(), 0, mem::size_of::<LineVertex>());
+/// gl_call!(gl::DrawArrays(gl::LINES, first as i32, vertices.len() as i32));
+/// ring.fence();
+/// ```
+pub struct RingBuffer<T: Pod> {
+    //  Declared before `vbo`, so the buffer is unmapped before it is deleted.
+    //  `None` when the buffer could not be mapped persistently.
+    mapping: Option<BufferMapping<'static, T, MapWrite>>,
+    vbo: Vbo<T>,
+    capacity: usize,
+    fences: Vec<GLsync>,
+    section: usize,
+}
+
+impl<T: Pod> RingBuffer<T> {
+    pub fn new(context: &GlContext, capacity: usize, sections: usize) -> RingBuffer<T> {
+        assert!(capacity > 0 && sections > 0, "a ring buffer can not be empty");
+
+        let len = capacity * sections;
+        let (mapping, vbo) = match map_persistently(context, len) {
+            Some((mapping, vbo)) => (Some(mapping), vbo),
+            None => {
+                log::warn!("Persistent mapping is not available, ring buffer writes are copied");
+                let mut vbo = Vbo::new(context);
+                vbo.allocate(len, BufferUsage::StreamDraw);
+                (None, vbo)
+            }
+        };
+
+        RingBuffer {
+            mapping,
+            vbo,
+            capacity,
+            fences: vec![ptr::null(); sections],
+            section: sections - 1,
+        }
+    }
+
+    /// Whether `write` goes straight into a persistent mapping.
+    pub fn is_mapped(&self) -> bool {
+        self.mapping.is_some()
+    }
+
+    /// Copies `data` into the next section and returns the index of its first
+    /// element in the whole buffer. Panics if `data` is longer than
+    /// `capacity`.
+    pub fn write(&mut self, data: &[T]) -> usize {
+        assert!(
+            data.len() <= self.capacity,
+            "{} elements do not fit into a ring buffer section of {}",
+            data.len(),
+            self.capacity
+        );
+
+        self.section = (self.section + 1) % self.fences.len();
+        wait_for(&mut self.fences[self.section]);
+
+        let first = self.section * self.capacity;
+        match &mut self.mapping {
+            Some(mapping) => mapping.write(first, data),
+            None => self.vbo.update_range(first, data),
+        }
+
+        first
+    }
+
+    /// Fences the current section after the draws reading it were issued, so
+    /// it is not overwritten before the GPU is done with it.
+    pub fn fence(&mut self) {
+        let previous = self.fences[self.section];
+        if !previous.is_null() {
+            gl_call!(gl::DeleteSync(previous));
+        }
+
+        let fence;
+        gl_call!(fence = gl::FenceSync(gl::SYNC_GPU_COMMANDS_COMPLETE, 0));
+        self.fences[self.section] = fence;
+    }
+
+    /// The byte offset of the current section in the buffer.
+    pub fn offset(&self) -> usize {
+        self.section * self.capacity * mem::size_of::<T>()
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    pub fn vbo(&self) -> &Vbo<T> {
+        &self.vbo
+    }
+}
+
+//  The mapping does not borrow the `Vbo`, the `RingBuffer` owning both keeps
+//  the buffer alive until the mapping is dropped.
+fn map_persistently<T: Pod>(
+    context: &GlContext,
+    len: usize,
+) -> Option<(BufferMapping<'static, T, MapWrite>, Vbo<T>)> {
+    if !supports(4, 4, "GL_ARB_buffer_storage") {
+        return None;
+    }
+
+    let mut vbo = Vbo::new(context);
+    vbo.allocate_storage(len, STORAGE_FLAGS);
+    let mapping = map_buffer_range(vbo.id(), 0, len, STORAGE_FLAGS)?;
+
+    Some((mapping, vbo))
+}
+
+fn wait_for(fence: &mut GLsync) {
+    if fence.is_null() {
+        return;
+    }
+
+    loop {
+        let result;
+        gl_call!(result = gl::ClientWaitSync(*fence, gl::SYNC_FLUSH_COMMANDS_BIT, 1_000_000_000));
+        match result {
+            gl::ALREADY_SIGNALED | gl::CONDITION_SATISFIED => break,
+            gl::TIMEOUT_EXPIRED => log::warn!("Still waiting for a ring buffer section"),
+            _ => {
+                log::error!("Waiting for a ring buffer section failed");
+                break;
+            }
+        }
+    }
+
+    gl_call!(gl::DeleteSync(*fence));
+    *fence = ptr::null();
+}
+
+impl<T: Pod> Drop for RingBuffer<T> {
+    fn drop(&mut self) {
+        for fence in self.fences.iter().filter(|fence| !fence.is_null()) {
+            gl_call!(gl::DeleteSync(*fence));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::backend::{record, GlCommand};
+
+    //  The recording backend reports version 0.0 without extensions, so the
+    //  ring buffer falls back to copying.
+    #[test]
+    fn writes_are_copied_without_persistent_mapping() {
+        let mut mapped = true;
+        let commands = record(|| {
+            let context = unsafe { GlContext::new() };
+            let mut ring: RingBuffer<f32> = RingBuffer::new(&context, 4, 2);
+            mapped = ring.is_mapped();
+
+            assert_eq!(ring.write(&[1.0, 2.0]), 0);
+            ring.fence();
+            assert_eq!(ring.write(&[3.0]), 4);
+        });
+
+        assert!(!mapped);
+        let writes: Vec<(GLintptr, GLsizeiptr)> = commands
+            .iter()
+            .filter_map(|command| match command {
+                GlCommand::NamedBufferSubData { offset, size, .. } => Some((*offset, *size)),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(writes, [(0, 8), (16, 4)]);
+    }
+}
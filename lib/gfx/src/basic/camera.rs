@@ -1,7 +1,23 @@
-use super::ShaderObject;
+use super::{ShaderObject, Std140, UniformBuffer};
+use crate::derives::Std140;
 use cgmath::{point3, prelude::*, vec3, Matrix4, Point3, SquareMatrix, Vector3};
 use logic::transform::{Movable, Rotable};
 
+/// The camera matrices as a std140 uniform block:
+///
+/// ```glsl
+/// layout (std140) uniform Camera
+/// {
+///     mat4 view;
+///     mat4 proj;
+/// };
+/// ```
+#[derive(Clone, Copy, Debug, Std140)]
+pub struct CameraUniforms {
+    pub view: Matrix4<f32>,
+    pub proj: Matrix4<f32>,
+}
+
 pub struct Camera {
     screen_rateo: f32,
 
@@ -103,6 +119,23 @@ impl Camera {
         shader.set_uniform("proj", self.proj_matrix);
     }
 
+    pub fn uniforms(&mut self) -> CameraUniforms {
+        self.update_proj_matrix();
+        self.update_view_matrix();
+
+        CameraUniforms {
+            view: self.view_matrix,
+            proj: self.proj_matrix,
+        }
+    }
+
+    /// Uploads the matrices to a uniform buffer, which can be shared by every
+    /// program with a `Camera` uniform block.
+    pub fn bind_to_buffer(&mut self, buffer: &mut UniformBuffer<CameraUniforms>) {
+        let uniforms = self.uniforms();
+        buffer.update(&uniforms);
+    }
+
     #[inline]
     pub fn move_forw(&mut self, amount: f32) {
         self.position += self.front * amount;
@@ -0,0 +1,212 @@
+use gl::types::*;
+use crate::gl_call;
+use std::{cell::RefCell, collections::HashSet, ffi::c_void, ptr, slice, sync::Mutex};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DebugSource {
+    Api,
+    WindowSystem,
+    ShaderCompiler,
+    ThirdParty,
+    Application,
+    Other,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DebugType {
+    Error,
+    DeprecatedBehavior,
+    UndefinedBehavior,
+    Portability,
+    Performance,
+    Marker,
+    PushGroup,
+    PopGroup,
+    Other,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum DebugSeverity {
+    Notification,
+    Low,
+    Medium,
+    High,
+}
+
+/// Selects which driver messages reach the log. Empty `sources` or `types`
+/// let everything through.
+#[derive(Clone, Debug)]
+pub struct DebugFilter {
+    pub sources: Vec<DebugSource>,
+    pub types: Vec<DebugType>,
+    pub min_severity: DebugSeverity,
+}
+
+static DEBUG_FILTER: Mutex<Option<DebugFilter>> = Mutex::new(None);
+
+impl DebugFilter {
+    pub fn accepts(&self, source: DebugSource, debug_type: DebugType, severity: DebugSeverity) -> bool {
+        (self.sources.is_empty() || self.sources.contains(&source))
+            && (self.types.is_empty() || self.types.contains(&debug_type))
+            && severity >= self.min_severity
+    }
+}
+
+impl Default for DebugFilter {
+    fn default() -> Self {
+        DebugFilter {
+            sources: Vec::new(),
+            types: Vec::new(),
+            min_severity: DebugSeverity::Low,
+        }
+    }
+}
+
+impl DebugSource {
+    fn from_gl(source: GLenum) -> DebugSource {
+        match source {
+            gl::DEBUG_SOURCE_API => DebugSource::Api,
+            gl::DEBUG_SOURCE_WINDOW_SYSTEM => DebugSource::WindowSystem,
+            gl::DEBUG_SOURCE_SHADER_COMPILER => DebugSource::ShaderCompiler,
+            gl::DEBUG_SOURCE_THIRD_PARTY => DebugSource::ThirdParty,
+            gl::DEBUG_SOURCE_APPLICATION => DebugSource::Application,
+            _ => DebugSource::Other,
+        }
+    }
+}
+
+impl DebugType {
+    fn from_gl(debug_type: GLenum) -> DebugType {
+        match debug_type {
+            gl::DEBUG_TYPE_ERROR => DebugType::Error,
+            gl::DEBUG_TYPE_DEPRECATED_BEHAVIOR => DebugType::DeprecatedBehavior,
+            gl::DEBUG_TYPE_UNDEFINED_BEHAVIOR => DebugType::UndefinedBehavior,
+            gl::DEBUG_TYPE_PORTABILITY => DebugType::Portability,
+            gl::DEBUG_TYPE_PERFORMANCE => DebugType::Performance,
+            gl::DEBUG_TYPE_MARKER => DebugType::Marker,
+            gl::DEBUG_TYPE_PUSH_GROUP => DebugType::PushGroup,
+            gl::DEBUG_TYPE_POP_GROUP => DebugType::PopGroup,
+            _ => DebugType::Other,
+        }
+    }
+}
+
+impl DebugSeverity {
+    fn from_gl(severity: GLenum) -> DebugSeverity {
+        match severity {
+            gl::DEBUG_SEVERITY_HIGH => DebugSeverity::High,
+            gl::DEBUG_SEVERITY_MEDIUM => DebugSeverity::Medium,
+            gl::DEBUG_SEVERITY_LOW => DebugSeverity::Low,
+            _ => DebugSeverity::Notification,
+        }
+    }
+
+    fn log_level(&self, debug_type: DebugType) -> log::Level {
+        match (self, debug_type) {
+            (_, DebugType::Error) | (DebugSeverity::High, _) => log::Level::Error,
+            (DebugSeverity::Medium, _) => log::Level::Warn,
+            (DebugSeverity::Low, _) => log::Level::Info,
+            (DebugSeverity::Notification, _) => log::Level::Debug,
+        }
+    }
+}
+
+/// Installs a `glDebugMessageCallback` that routes driver messages into `log`.
+/// Needs a context created with the debug flag (see `Window::new_debug`) to
+/// receive everything. Returns false when KHR_debug is not available.
+pub fn enable_debug_output(filter: DebugFilter) -> bool {
+    if !gl::DebugMessageCallback::is_loaded() {
+        log::warn!("KHR_debug is not supported by this context");
+        return false;
+    }
+
+    *DEBUG_FILTER.lock().unwrap() = Some(filter);
+
+    gl_call!(gl::Enable(gl::DEBUG_OUTPUT));
+    gl_call!(gl::Enable(gl::DEBUG_OUTPUT_SYNCHRONOUS));
+    gl_call!(gl::DebugMessageCallback(Some(debug_callback), ptr::null()));
+
+    true
+}
+
+pub fn disable_debug_output() {
+    if gl::DebugMessageCallback::is_loaded() {
+        gl_call!(gl::DebugMessageCallback(None, ptr::null()));
+        gl_call!(gl::Disable(gl::DEBUG_OUTPUT));
+    }
+
+    *DEBUG_FILTER.lock().unwrap() = None;
+}
+
+/// Names a gl object so driver messages mention it. Does nothing when
+/// KHR_debug is not available.
+pub(crate) fn object_label(identifier: GLenum, name: GLuint, label: &str) {
+    if gl::ObjectLabel::is_loaded() {
+        gl_call!(gl::ObjectLabel(
+            identifier,
+            name,
+            label.len() as GLsizei,
+            label.as_ptr() as *const GLchar
+        ));
+    }
+}
+
+thread_local! {
+    //  Every gl object currently owned by a wrapper, keyed by its namespace
+    //  (`gl::BUFFER`, `gl::TEXTURE`, ...). Only filled in debug builds.
+    static LIVE_OBJECTS: RefCell<HashSet<(GLenum, GLuint)>> = RefCell::new(HashSet::new());
+}
+
+/// Records that a wrapper took ownership of a gl object. Does nothing in
+/// release builds.
+pub(crate) fn track_object(namespace: GLenum, name: GLuint) {
+    if cfg!(debug_assertions) && name != 0 {
+        LIVE_OBJECTS.with(|objects| objects.borrow_mut().insert((namespace, name)));
+    }
+}
+
+/// Records that a wrapper deleted its gl object, and reports the delete if
+/// that object was not owned by anyone anymore. Does nothing in release
+/// builds.
+pub(crate) fn untrack_object(namespace: GLenum, name: GLuint) {
+    if cfg!(debug_assertions) && name != 0 {
+        let owned = LIVE_OBJECTS.with(|objects| objects.borrow_mut().remove(&(namespace, name)));
+        if !owned {
+            log::error!("gl object {} (namespace {:#x}) is deleted twice", name, namespace);
+        }
+    }
+}
+
+extern "system" fn debug_callback(
+    source: GLenum,
+    debug_type: GLenum,
+    id: GLuint,
+    severity: GLenum,
+    length: GLsizei,
+    message: *const GLchar,
+    _user_param: *mut c_void,
+) {
+    let source = DebugSource::from_gl(source);
+    let debug_type = DebugType::from_gl(debug_type);
+    let severity = DebugSeverity::from_gl(severity);
+
+    let accepted = match DEBUG_FILTER.lock() {
+        Ok(filter) => filter
+            .as_ref()
+            .is_some_and(|filter| filter.accepts(source, debug_type, severity)),
+        Err(_) => false,
+    };
+    if !accepted || message.is_null() {
+        return;
+    }
+
+    let message = unsafe { slice::from_raw_parts(message as *const u8, length.max(0) as usize) };
+    log::log!(
+        severity.log_level(debug_type),
+        "GlDebug [{:?} {:?} {}]: {}",
+        source,
+        debug_type,
+        id,
+        String::from_utf8_lossy(message)
+    );
+}
@@ -0,0 +1,71 @@
+use gl::types::*;
+use crate::basic::Pod;
+
+/// The type of the elements of an index buffer.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum IndexType {
+    U8,
+    U16,
+    U32,
+}
+
+impl IndexType {
+    pub fn gl_type(&self) -> GLenum {
+        match self {
+            IndexType::U8 => gl::UNSIGNED_BYTE,
+            IndexType::U16 => gl::UNSIGNED_SHORT,
+            IndexType::U32 => gl::UNSIGNED_INT,
+        }
+    }
+
+    /// The size of an index in bytes.
+    pub fn size(&self) -> usize {
+        match self {
+            IndexType::U8 => 1,
+            IndexType::U16 => 2,
+            IndexType::U32 => 4,
+        }
+    }
+
+    /// The smallest type that can index `vertex_count` vertices.
+    pub fn for_vertex_count(vertex_count: usize) -> IndexType {
+        if vertex_count <= u8::MAX as usize + 1 {
+            IndexType::U8
+        } else if vertex_count <= u16::MAX as usize + 1 {
+            IndexType::U16
+        } else {
+            IndexType::U32
+        }
+    }
+}
+
+/// An element type of an index buffer.
+pub trait Index: Pod {
+    const INDEX_TYPE: IndexType;
+}
+
+impl Index for u8 {
+    const INDEX_TYPE: IndexType = IndexType::U8;
+}
+
+impl Index for u16 {
+    const INDEX_TYPE: IndexType = IndexType::U16;
+}
+
+impl Index for u32 {
+    const INDEX_TYPE: IndexType = IndexType::U32;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn for_vertex_count_picks_the_smallest_type() {
+        assert_eq!(IndexType::for_vertex_count(0), IndexType::U8);
+        assert_eq!(IndexType::for_vertex_count(256), IndexType::U8);
+        assert_eq!(IndexType::for_vertex_count(257), IndexType::U16);
+        assert_eq!(IndexType::for_vertex_count(65536), IndexType::U16);
+        assert_eq!(IndexType::for_vertex_count(65537), IndexType::U32);
+    }
+}
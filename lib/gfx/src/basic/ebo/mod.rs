@@ -1,5 +1,7 @@
 mod ebo;
 mod ebo_object;
+mod index;
 
 pub use ebo::Ebo;
-pub use ebo_object::EboObject;
\ No newline at end of file
+pub use ebo_object::EboObject;
+pub use index::{Index, IndexType};
\ No newline at end of file
@@ -0,0 +1,38 @@
+//  Declares an enum over a set of gl constants. Every enum gets a `Raw`
+//  variant for values that are not listed (extensions, newer versions), so
+//  nothing that OpenGL accepts is out of reach.
+macro_rules! gl_enum {
+    (
+        $(#[$meta:meta])*
+        pub enum $name:ident {
+            $($(#[$variant_meta:meta])* $variant:ident => $value:path),+ $(,)?
+        }
+    ) => {
+        $(#[$meta])*
+        #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+        pub enum $name {
+            $($(#[$variant_meta])* $variant,)+
+            /// Any other value, passed to OpenGL as is.
+            Raw(gl::types::GLenum),
+        }
+
+        impl $name {
+            pub fn gl_type(&self) -> gl::types::GLenum {
+                match self {
+                    $($name::$variant => $value,)+
+                    $name::Raw(value) => *value,
+                }
+            }
+
+            /// The variant for `value`, or `Raw` if it has none.
+            pub fn from_gl(value: gl::types::GLenum) -> $name {
+                match value {
+                    $($value => $name::$variant,)+
+                    _ => $name::Raw(value),
+                }
+            }
+        }
+    };
+}
+
+pub(crate) use gl_enum;
@@ -0,0 +1,78 @@
+use crate::{
+    basic::{BufferUsage, Vao, VaoLayout, Vbo, Vertex},
+    prelude::{Bindable, Labelable},
+    GlContext,
+};
+
+/// A `Vbo` of per-instance attributes, meant to be rewritten every frame.
+/// `T` is laid out like a vertex struct, use `#[vertex(location = N)]` so its
+/// locations follow the ones of the mesh vertices.
+pub struct InstanceBuffer<T: Vertex> {
+    vbo: Vbo<T>,
+}
+
+impl<T: Vertex> InstanceBuffer<T> {
+    pub fn new(context: &GlContext) -> InstanceBuffer<T> {
+        InstanceBuffer { vbo: Vbo::new(context) }
+    }
+
+    /// Replaces the instances. The storage is reallocated when it grows and
+    /// orphaned otherwise, so the driver does not wait for draws still
+    /// reading the previous frame's data.
+    pub fn update(&mut self, instances: &[T]) {
+        let capacity = self.vbo.capacity().max(instances.len());
+        self.vbo.allocate(capacity, BufferUsage::StreamDraw);
+        self.vbo.update_range(0, instances);
+    }
+
+    /// The number of instances of the last `update`.
+    pub fn len(&self) -> usize {
+        self.vbo.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.vbo.is_empty()
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.vbo.capacity()
+    }
+
+    /// The layout of `T`, advancing once per instance. Every field of `T`
+    /// shares the divisor, see `VaoLayout::set_divisor`.
+    pub fn layout(&self) -> VaoLayout {
+        let mut layout = T::layout();
+        layout.set_divisor(1);
+
+        layout
+    }
+
+    /// Points the per-instance attributes of `vao` at this buffer.
+    pub fn apply_layout(&self, vao: &Vao) {
+        vao.bind();
+        self.bind();
+        self.layout().apply_layout_raw();
+    }
+
+    pub fn vbo(&self) -> &Vbo<T> {
+        &self.vbo
+    }
+}
+
+impl<T: Vertex> Bindable for InstanceBuffer<T> {
+    #[inline]
+    fn bind(&self) {
+        self.vbo.bind();
+    }
+
+    #[inline]
+    fn unbind(&self) {
+        self.vbo.unbind();
+    }
+}
+
+impl<T: Vertex> Labelable for InstanceBuffer<T> {
+    fn label(&self, label: &str) {
+        self.vbo.label(label);
+    }
+}
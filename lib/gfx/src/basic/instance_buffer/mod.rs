@@ -0,0 +1,3 @@
+mod instance_buffer;
+
+pub use instance_buffer::InstanceBuffer;
@@ -1,15 +1,42 @@
+mod buffer;
 mod camera;
+mod debug;
 mod ebo;
+mod gl_enum;
+mod instance_buffer;
 mod shader;
+mod shared;
 mod texture;
+mod uniform_buffer;
 mod vao;
 mod vbo;
 
-pub use camera::Camera;
-pub use ebo::{Ebo, EboObject};
-pub use shader::{Shader, ShaderObject, ShaderUniform};
-pub use texture::{TextureData, Texture, TextureBuilder, TextureObject};
-pub use vao::{Vao, VaoLayout, VaoObject};
+pub use buffer::{
+    cast_slice, ArrayTarget, Buffer, BufferMapping, BufferTarget, BufferUsage, DrawIndirectTarget,
+    ElementArrayTarget, IndexedBufferTarget, MapAccess, MapRead, MapReadWrite, MapWrite, Pod,
+    RingBuffer, ShaderStorageTarget, UniformTarget,
+};
+pub use camera::{Camera, CameraUniforms};
+pub use debug::{
+    disable_debug_output, enable_debug_output, DebugFilter, DebugSeverity, DebugSource, DebugType,
+};
+pub(crate) use debug::{object_label, track_object, untrack_object};
+pub(crate) use gl_enum::gl_enum;
+pub use ebo::{Ebo, EboObject, Index, IndexType};
+pub use instance_buffer::InstanceBuffer;
+pub use shader::{
+    glsl_type_name, memory_barrier, memory_barrier_by_region, AttributeInfo, ComputeProgram,
+    PreprocessedShader, ProgramCache, Shader, ShaderBuilder, ShaderDiagnostic, ShaderError,
+    ShaderFiles, ShaderObject, ShaderPreprocessor, ShaderReflection, ShaderStage, ShaderUniform,
+    UniformElement, UniformInfo, Uniforms,
+};
+pub use shared::Shared;
+pub use texture::{Filter, TextureData, Texture, TextureBuilder, TextureFormat, TextureObject, Wrap};
+pub use uniform_buffer::{Std140, UniformBuffer};
+pub use vao::{
+    AttribType, AttributeKind, LayoutError, LayoutMismatch, Vao, VaoLayout, VaoObject, Vertex,
+    VertexAttribute, VertexComponent, VertexStream,
+};
 pub use vbo::{Vbo, VboObject};
 
 pub trait Bindable {
@@ -17,6 +44,10 @@ pub trait Bindable {
     fn unbind(&self);
 }
 
+pub trait Labelable {
+    fn label(&self, label: &str);
+}
+
 pub trait Drawable: Bindable {
     fn draw(&self);
 
@@ -0,0 +1,125 @@
+use gl::types::*;
+use super::{Shader, ShaderBuilder, ShaderError, ShaderUniform, UniformInfo};
+use crate::{
+    prelude::{gl_call, Bindable, Labelable, ShaderObject},
+    GlContext,
+};
+use std::{any::TypeId, path::Path, rc::Rc};
+
+/// A program made of a single compute shader.
+pub struct ComputeProgram {
+    shader: Shader,
+}
+
+impl ComputeProgram {
+    pub fn new(context: &GlContext, source: &Path) -> Result<ComputeProgram, ShaderError> {
+        ShaderBuilder::new().compute(source).build_compute(context)
+    }
+
+    pub(crate) fn from_shader(shader: Shader) -> ComputeProgram {
+        ComputeProgram { shader }
+    }
+
+    /// Binds the program and runs `x * y * z` work groups.
+    pub fn dispatch(&self, x: GLuint, y: GLuint, z: GLuint) {
+        self.bind();
+        gl_call!(gl::DispatchCompute(x, y, z));
+    }
+
+    /// Like `dispatch`, followed by `memory_barrier(barriers)` so the results
+    /// are visible to the next commands.
+    pub fn dispatch_with_barrier(&self, x: GLuint, y: GLuint, z: GLuint, barriers: GLbitfield) {
+        self.dispatch(x, y, z);
+        memory_barrier(barriers);
+    }
+
+    /// The `local_size_x/y/z` declared in the shader.
+    pub fn work_group_size(&self) -> [GLint; 3] {
+        let mut size = [0; 3];
+        gl_call!(gl::GetProgramiv(
+            self.shader.id(),
+            gl::COMPUTE_WORK_GROUP_SIZE,
+            size.as_mut_ptr()
+        ));
+
+        size
+    }
+
+    pub fn shader(&self) -> &Shader {
+        &self.shader
+    }
+
+    /// See `Shader::watch`.
+    pub fn watch(&mut self) {
+        self.shader.watch();
+    }
+
+    /// See `Shader::reload_if_changed`.
+    pub fn reload_if_changed(&mut self) -> Result<bool, ShaderError> {
+        self.shader.reload_if_changed()
+    }
+
+    /// See `Shader::bind_uniform_block`.
+    pub fn bind_uniform_block(&mut self, block_name: &str, binding: GLuint) {
+        self.shader.bind_uniform_block(block_name, binding);
+    }
+}
+
+impl Bindable for ComputeProgram {
+    #[inline]
+    fn bind(&self) {
+        self.shader.bind();
+    }
+
+    #[inline]
+    fn unbind(&self) {
+        self.shader.unbind();
+    }
+}
+
+impl Labelable for ComputeProgram {
+    fn label(&self, label: &str) {
+        self.shader.label(label);
+    }
+}
+
+impl ShaderObject for ComputeProgram {
+    #[inline]
+    fn set_uniform<T: ShaderUniform>(&mut self, uniform_name: &str, value: T) {
+        self.shader.set_uniform(uniform_name, value);
+    }
+
+    #[inline]
+    fn get_uniform_location(&mut self, uniform_name: &str) -> GLint {
+        self.shader.get_uniform_location(uniform_name)
+    }
+
+    #[inline]
+    fn uniform_info(&self, uniform_name: &str) -> Option<&UniformInfo> {
+        self.shader.uniform_info(uniform_name)
+    }
+
+    #[inline]
+    fn cached_uniform_locations(&self, key: TypeId) -> Option<Rc<[GLint]>> {
+        self.shader.cached_uniform_locations(key)
+    }
+
+    #[inline]
+    fn cache_uniform_locations(&mut self, key: TypeId, locations: Rc<[GLint]>) {
+        self.shader.cache_uniform_locations(key, locations);
+    }
+}
+
+/// Makes writes of earlier shaders (compute or not) visible to the commands
+/// selected by `barriers`, e.g. `gl::SHADER_STORAGE_BARRIER_BIT` before
+/// reading a storage buffer again or `gl::VERTEX_ATTRIB_ARRAY_BARRIER_BIT`
+/// before drawing from it.
+pub fn memory_barrier(barriers: GLbitfield) {
+    gl_call!(gl::MemoryBarrier(barriers));
+}
+
+/// Like `memory_barrier`, but only orders accesses within the same
+/// framebuffer region, which can be cheaper for fragment shader writes.
+pub fn memory_barrier_by_region(barriers: GLbitfield) {
+    gl_call!(gl::MemoryBarrierByRegion(barriers));
+}
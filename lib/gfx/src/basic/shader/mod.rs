@@ -1,7 +1,25 @@
+mod compute_program;
+mod program_cache;
 mod shader;
+mod shader_builder;
+mod shader_error;
+mod shader_files;
 mod shader_object;
+mod shader_preprocessor;
+mod shader_reflection;
+mod shader_stage;
 mod shader_uniform;
+mod uniforms;
 
+pub use compute_program::{memory_barrier, memory_barrier_by_region, ComputeProgram};
+pub use program_cache::ProgramCache;
 pub use shader::Shader;
+pub use shader_builder::ShaderBuilder;
+pub use shader_error::{ShaderDiagnostic, ShaderError};
+pub use shader_files::ShaderFiles;
 pub use shader_object::ShaderObject;
-pub use shader_uniform::ShaderUniform;
\ No newline at end of file
+pub use shader_preprocessor::{PreprocessedShader, ShaderPreprocessor};
+pub use shader_reflection::{glsl_type_name, AttributeInfo, ShaderReflection, UniformInfo};
+pub use shader_stage::ShaderStage;
+pub use shader_uniform::{ShaderUniform, UniformElement};
+pub use uniforms::Uniforms;
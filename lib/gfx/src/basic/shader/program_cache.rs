@@ -0,0 +1,210 @@
+use gl::types::*;
+use super::ShaderStage;
+use crate::gl_call;
+use std::{
+    collections::hash_map::DefaultHasher,
+    ffi::{c_void, CStr},
+    fs,
+    hash::{Hash, Hasher},
+    path::{Path, PathBuf},
+};
+
+const MAGIC: &[u8; 4] = b"GLPB";
+
+/// A directory of linked program binaries (`glGetProgramBinary`). Entries
+/// are keyed by the preprocessed sources and the driver, a binary the driver
+/// rejects is deleted and the program is compiled normally instead.
+#[derive(Clone, Debug)]
+pub struct ProgramCache {
+    directory: PathBuf,
+}
+
+impl ProgramCache {
+    pub fn new<P: Into<PathBuf>>(directory: P) -> ProgramCache {
+        ProgramCache {
+            directory: directory.into(),
+        }
+    }
+
+    pub fn directory(&self) -> &Path {
+        &self.directory
+    }
+
+    /// Whether the driver supports any binary format at all.
+    pub fn is_supported() -> bool {
+        let mut formats = 0;
+        gl_call!(gl::GetIntegerv(gl::NUM_PROGRAM_BINARY_FORMATS, &mut formats));
+
+        formats > 0
+    }
+
+    pub(crate) fn key(&self, sources: &[(ShaderStage, &str)]) -> u64 {
+        //  `DefaultHasher` is only stable within the same Rust release, a new
+        //  release just starts with a cold cache.
+        let mut hasher = DefaultHasher::new();
+        for (stage, code) in sources {
+            stage.gl_type().hash(&mut hasher);
+            code.hash(&mut hasher);
+        }
+        for name in [gl::VENDOR, gl::RENDERER, gl::VERSION].iter() {
+            driver_string(*name).hash(&mut hasher);
+        }
+
+        hasher.finish()
+    }
+
+    /// Creates a program from the stored binary. Returns `None` when there
+    /// is no entry or the driver rejected it.
+    pub(crate) fn load(&self, key: u64) -> Option<GLuint> {
+        let path = self.entry_path(key);
+        let content = fs::read(&path).ok()?;
+
+        let binary = match parse_entry(&content) {
+            Some(binary) => binary,
+            None => {
+                log::warn!("Ignoring corrupt program binary {}", path.display());
+                let _ = fs::remove_file(&path);
+                return None;
+            }
+        };
+
+        let program_id;
+        gl_call!(program_id = gl::CreateProgram());
+        gl_call!(gl::ProgramBinary(
+            program_id,
+            binary.0,
+            binary.1.as_ptr() as *const c_void,
+            binary.1.len() as GLsizei
+        ));
+
+        let mut status = 0;
+        gl_call!(gl::GetProgramiv(program_id, gl::LINK_STATUS, &mut status));
+        if status == 0 {
+            log::info!("Program binary {} is stale, recompiling", path.display());
+            gl_call!(gl::DeleteProgram(program_id));
+            let _ = fs::remove_file(&path);
+            return None;
+        }
+
+        Some(program_id)
+    }
+
+    /// Call before linking, some drivers only keep the binary around when
+    /// asked to.
+    pub(crate) fn prepare(&self, program_id: GLuint) {
+        gl_call!(gl::ProgramParameteri(
+            program_id,
+            gl::PROGRAM_BINARY_RETRIEVABLE_HINT,
+            gl::TRUE as GLint
+        ));
+    }
+
+    /// Stores the binary of a linked program. Failures are only logged, the
+    /// cache is an optimization.
+    pub(crate) fn store(&self, key: u64, program_id: GLuint) {
+        let mut length = 0;
+        gl_call!(gl::GetProgramiv(program_id, gl::PROGRAM_BINARY_LENGTH, &mut length));
+        if length <= 0 {
+            return;
+        }
+
+        let mut binary: Vec<u8> = vec![0; length as usize];
+        let mut format = 0;
+        gl_call!(gl::GetProgramBinary(
+            program_id,
+            length,
+            &mut length,
+            &mut format,
+            binary.as_mut_ptr() as *mut c_void
+        ));
+        binary.truncate(length.max(0) as usize);
+
+        let mut content = Vec::with_capacity(MAGIC.len() + 4 + binary.len());
+        content.extend_from_slice(MAGIC);
+        content.extend_from_slice(&format.to_le_bytes());
+        content.extend_from_slice(&binary);
+
+        let path = self.entry_path(key);
+        if let Err(error) =
+            fs::create_dir_all(&self.directory).and_then(|_| fs::write(&path, content))
+        {
+            log::warn!("Could not write program binary {}: {}", path.display(), error);
+        }
+    }
+
+    fn entry_path(&self, key: u64) -> PathBuf {
+        self.directory.join(format!("{:016x}.bin", key))
+    }
+}
+
+fn parse_entry(content: &[u8]) -> Option<(GLenum, &[u8])> {
+    let content = content.strip_prefix(MAGIC)?;
+    if content.len() <= 4 {
+        return None;
+    }
+
+    let (format, binary) = content.split_at(4);
+    let format = GLenum::from_le_bytes([format[0], format[1], format[2], format[3]]);
+
+    Some((format, binary))
+}
+
+fn driver_string(name: GLenum) -> String {
+    let string;
+    gl_call!(string = gl::GetString(name));
+    if string.is_null() {
+        return String::new();
+    }
+
+    unsafe { CStr::from_ptr(string as *const _) }
+        .to_string_lossy()
+        .into_owned()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::backend::{record, GlCommand};
+
+    #[test]
+    fn parse_entry_reads_the_format_and_binary() {
+        let mut content = MAGIC.to_vec();
+        content.extend_from_slice(&0x8741u32.to_le_bytes());
+        content.extend_from_slice(&[1, 2, 3]);
+
+        assert_eq!(parse_entry(&content), Some((0x8741, &[1u8, 2, 3][..])));
+    }
+
+    #[test]
+    fn parse_entry_rejects_a_wrong_magic() {
+        let mut content = b"GLPX".to_vec();
+        content.extend_from_slice(&0x8741u32.to_le_bytes());
+        content.extend_from_slice(&[1, 2, 3]);
+
+        assert_eq!(parse_entry(&content), None);
+    }
+
+    #[test]
+    fn parse_entry_rejects_truncated_entries() {
+        assert_eq!(parse_entry(b"GL"), None);
+        assert_eq!(parse_entry(b"GLPB"), None);
+        assert_eq!(parse_entry(b"GLPB\x41\x87\x00\x00"), None);
+    }
+
+    #[test]
+    fn corrupt_entries_are_removed_and_not_loaded() {
+        let directory = std::env::temp_dir().join(format!("gfx-program-cache-{}", std::process::id()));
+        let cache = ProgramCache::new(&directory);
+        fs::create_dir_all(&directory).unwrap();
+        fs::write(cache.entry_path(42), b"GLPB\x41").unwrap();
+
+        let mut loaded = Some(0);
+        let commands = record(|| loaded = cache.load(42));
+        let removed = !cache.entry_path(42).exists();
+        fs::remove_dir_all(&directory).unwrap();
+
+        assert_eq!(loaded, None);
+        assert!(removed);
+        assert!(!commands.iter().any(|command| matches!(command, GlCommand::CreateProgram {})));
+    }
+}
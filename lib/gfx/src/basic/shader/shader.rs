@@ -1,76 +1,209 @@
 extern crate gl;
 
 use self::gl::types::*;
-use super::ShaderUniform;
-use crate::prelude::{Bindable, ShaderObject};
-use crate::gl_call;
-
-use log::{error, warn};
-use std::{ffi::CString, fs, path::Path, ptr};
+use super::{
+    shader_reflection::active_uniforms, ProgramCache, ShaderBuilder, ShaderDiagnostic, ShaderError, ShaderFiles,
+    ShaderPreprocessor, ShaderReflection, ShaderStage, ShaderUniform, UniformInfo,
+};
+use crate::basic::{object_label, track_object};
+use crate::prelude::{Bindable, Labelable, ShaderObject};
+use crate::{delete_object, gl_call, GlContext};
+
+use std::{
+    any::TypeId,
+    collections::{HashMap, HashSet},
+    ffi::CString,
+    fs, io,
+    path::{Path, PathBuf},
+    ptr,
+    rc::Rc,
+    time::SystemTime,
+};
 use utils::str_to_c_str_ptr;
 
-#[derive(Clone)]
 pub struct Shader{
     shader_id: GLuint,
-    uniforms: Vec::<(String, GLint)>
+    reflection: ShaderReflection,
+    //  Names that were asked for but are not active, so they are only
+    //  reported once.
+    missing_uniforms: HashSet<String>,
+    //  Field locations of `Uniforms` types.
+    uniform_locations: HashMap<TypeId, Rc<[GLint]>>,
+    uniform_blocks: Vec<(String, GLuint)>,
+
+    sources: Vec<(ShaderStage, PathBuf)>,
+    preprocessor: ShaderPreprocessor,
+    cache: Option<ProgramCache>,
+    //  Every file the program was built from, includes too.
+    files: Vec<PathBuf>,
+    //  Modification times of `files`, only tracked while watching.
+    watched: Option<Vec<Option<SystemTime>>>,
 }
 
 #[allow(dead_code)]
 impl Shader {
-    pub fn new(vertex_source: &Path, fragment_source: &Path) -> Shader {
-        let vertex_code = read_shader_source(vertex_source.as_os_str().to_str().unwrap()).unwrap();
-        let fragment_code =
-            read_shader_source(fragment_source.as_os_str().to_str().unwrap()).unwrap();
-        let vertex_code = CString::new(vertex_code.as_bytes()).unwrap();
-        let fragment_code = CString::new(fragment_code.as_bytes()).unwrap();
-
-        let shader_id = {
-            let shader_id;
-            let vertex_shader;
-            let fragment_shader;
-
-            gl_call!(shader_id = gl::CreateProgram());
-
-            gl_call!(vertex_shader = gl::CreateShader(gl::VERTEX_SHADER));
-            gl_call!(fragment_shader = gl::CreateShader(gl::FRAGMENT_SHADER));
-
-            gl_call!(gl::ShaderSource(
-                vertex_shader,
-                1,
-                &vertex_code.as_ptr(),
-                ptr::null()
-            ));
-            gl_call!(gl::ShaderSource(
-                fragment_shader,
-                1,
-                &fragment_code.as_ptr(),
-                ptr::null()
-            ));
-
-            gl_call!(gl::CompileShader(vertex_shader));
-            check_shader_error(vertex_shader, &vertex_source);
-
-            gl_call!(gl::CompileShader(fragment_shader));
-            check_shader_error(fragment_shader, &fragment_source);
-
-            gl_call!(gl::AttachShader(shader_id, vertex_shader));
-            gl_call!(gl::AttachShader(shader_id, fragment_shader));
-            gl_call!(gl::LinkProgram(shader_id));
-            check_program_error(shader_id, &vertex_source, &fragment_source);
-
-            gl_call!(gl::DeleteShader(vertex_shader));
-            gl_call!(gl::DeleteShader(fragment_shader));
+    pub fn new(context: &GlContext, vertex_source: &Path, fragment_source: &Path) -> Result<Shader, ShaderError> {
+        Shader::with_preprocessor(context, vertex_source, fragment_source, ShaderPreprocessor::new())
+    }
 
-            shader_id
-        };
+    /// Like `new`, but injects `#define name value` for every pair right after
+    /// the `#version` line of both stages.
+    pub fn with_defines(
+        context: &GlContext,
+        vertex_source: &Path,
+        fragment_source: &Path,
+        defines: &[(&str, &str)],
+    ) -> Result<Shader, ShaderError> {
+        let preprocessor = defines
+            .iter()
+            .fold(ShaderPreprocessor::new(), |preprocessor, (name, value)| {
+                preprocessor.define(name, value)
+            });
+
+        Shader::with_preprocessor(context, vertex_source, fragment_source, preprocessor)
+    }
+
+    pub fn with_preprocessor(
+        context: &GlContext,
+        vertex_source: &Path,
+        fragment_source: &Path,
+        preprocessor: ShaderPreprocessor,
+    ) -> Result<Shader, ShaderError> {
+        ShaderBuilder::new()
+            .vertex(vertex_source)
+            .fragment(fragment_source)
+            .preprocessor(preprocessor)
+            .build(context)
+    }
+
+    /// Builds a program from GLSL code instead of files. Includes are not
+    /// available, as there is no directory to resolve them against.
+    pub fn from_sources(
+        context: &GlContext,
+        vertex_code: &str,
+        fragment_code: &str,
+    ) -> Result<Shader, ShaderError> {
+        let files = ShaderFiles::in_memory(vec![
+            (PathBuf::from("<vertex>"), vertex_code.to_string()),
+            (PathBuf::from("<fragment>"), fragment_code.to_string()),
+        ]);
+
+        files.shader(context, Path::new("<vertex>"), Path::new("<fragment>"))
+    }
 
-        Shader{
+    //  The stages are checked by `ShaderBuilder`.
+    pub(crate) fn link(
+        sources: Vec<(ShaderStage, PathBuf)>,
+        preprocessor: ShaderPreprocessor,
+        cache: Option<ProgramCache>,
+    ) -> Result<Shader, ShaderError> {
+        let (shader_id, files) = link_program(&sources, &preprocessor, cache.as_ref())?;
+        track_object(gl::PROGRAM, shader_id);
+
+        Ok(Shader{
             shader_id,
-            uniforms: Vec::new(),
-        }
+            reflection: ShaderReflection::new(shader_id),
+            missing_uniforms: HashSet::new(),
+            uniform_locations: HashMap::new(),
+            uniform_blocks: Vec::new(),
+            sources,
+            preprocessor,
+            cache,
+            files,
+            watched: None,
+        })
+    }
+
+    pub(crate) fn id(&self) -> GLuint {
+        self.shader_id
+    }
+
+    pub fn sources(&self) -> &[(ShaderStage, PathBuf)] {
+        &self.sources
+    }
+
+    pub fn reflection(&self) -> &ShaderReflection {
+        &self.reflection
+    }
+
+    /// Makes the uniform block `block_name` read from the uniform buffer
+    /// bound to `binding` (see `UniformBuffer::bind_base`). The binding is
+    /// kept across reloads.
+    pub fn bind_uniform_block(&mut self, block_name: &str, binding: GLuint) {
+        uniform_block_binding(self.shader_id, block_name, binding);
+
+        self.uniform_blocks.retain(|(name, _)| name != block_name);
+        self.uniform_blocks.push((block_name.to_string(), binding));
+    }
+
+    /// The stage sources and every file they include.
+    pub fn files(&self) -> &[PathBuf] {
+        &self.files
     }
 
+    /// Starts tracking the source files and their includes, so that `reload_if_changed` can pick
+    /// up edits.
+    pub fn watch(&mut self) {
+        self.watched = Some(modification_times(&self.files));
+    }
 
+    pub fn unwatch(&mut self) {
+        self.watched = None;
+    }
+
+    /// Recompiles the program if a watched source changed since the last
+    /// check. Returns whether the program was replaced. On error the old
+    /// program stays in use.
+    pub fn reload_if_changed(&mut self) -> Result<bool, ShaderError> {
+        let current_times = match &self.watched {
+            Some(times) => {
+                let current_times = modification_times(&self.files);
+                if *times == current_times {
+                    return Ok(false);
+                }
+
+                current_times
+            }
+            None => return Ok(false),
+        };
+
+        //  Remember the new times even if the compilation fails, otherwise we
+        //  would recompile the broken source every frame.
+        self.watched = Some(current_times);
+        self.reload()?;
+
+        Ok(true)
+    }
+
+    /// Recompiles the program from its sources and swaps it in, carrying the
+    /// current uniform values over. On error the old program stays in use.
+    pub fn reload(&mut self) -> Result<(), ShaderError> {
+        let (shader_id, files) =
+            link_program(&self.sources, &self.preprocessor, self.cache.as_ref())?;
+
+        copy_uniform_values(self.shader_id, shader_id);
+        delete_object(gl::PROGRAM, self.shader_id);
+        track_object(gl::PROGRAM, shader_id);
+
+        self.shader_id = shader_id;
+        self.reflection = ShaderReflection::new(shader_id);
+        self.missing_uniforms.clear();
+        self.uniform_locations.clear();
+        for (block_name, binding) in self.uniform_blocks.iter() {
+            uniform_block_binding(shader_id, block_name, *binding);
+        }
+
+        //  Includes may have been added or removed.
+        if files != self.files {
+            if self.watched.is_some() {
+                self.watched = Some(modification_times(&files));
+            }
+            self.files = files;
+        }
+
+        log::info!("Reloaded shader {}", shader_id);
+        Ok(())
+    }
 }
 
 impl ShaderObject for Shader {
@@ -80,15 +213,31 @@ impl ShaderObject for Shader {
     }
 
     fn get_uniform_location(&mut self, uniform_name: &str) -> GLint {
-        let uniform_name_string = uniform_name.to_string();
-        if let Some((_, uniform_location)) = self.uniforms.iter().find(|x| x.0 == uniform_name_string) {
-            return *uniform_location;
+        if let Some(uniform) = self.reflection.uniform(uniform_name) {
+            return uniform.location;
         }
-        
-        let uniform_location = get_uniform_location(self.shader_id, uniform_name);
-        self.uniforms.push((uniform_name_string, uniform_location));
 
-        uniform_location
+        if self.missing_uniforms.insert(uniform_name.to_string()) {
+            log::warn!(
+                "Could not find uniform {} in shader {}",
+                uniform_name,
+                self.shader_id
+            );
+        }
+
+        -1
+    }
+
+    fn uniform_info(&self, uniform_name: &str) -> Option<&UniformInfo> {
+        self.reflection.uniform(uniform_name)
+    }
+
+    fn cached_uniform_locations(&self, key: TypeId) -> Option<Rc<[GLint]>> {
+        self.uniform_locations.get(&key).cloned()
+    }
+
+    fn cache_uniform_locations(&mut self, key: TypeId, locations: Rc<[GLint]>) {
+        self.uniform_locations.insert(key, locations);
     }
 }
 
@@ -104,92 +253,289 @@ impl Bindable for Shader {
     }
 }
 
+impl Labelable for Shader {
+    fn label(&self, label: &str) {
+        object_label(gl::PROGRAM, self.shader_id, label);
+    }
+}
+
 impl Drop for Shader {
     fn drop(&mut self) {
-        gl_call!(gl::DeleteProgram(self.shader_id));
+        delete_object(gl::PROGRAM, self.shader_id);
     }
 }
 
-fn read_shader_source(source: &str) -> Option<String> {
-    let code = fs::read_to_string(source);
+//  Returns the program and every file that went into it.
+fn link_program(
+    sources: &[(ShaderStage, PathBuf)],
+    preprocessor: &ShaderPreprocessor,
+    cache: Option<&ProgramCache>,
+) -> Result<(GLuint, Vec<PathBuf>), ShaderError> {
+    let preprocessed = sources
+        .iter()
+        .map(|(_, path)| preprocessor.process(path))
+        .collect::<Result<Vec<_>, _>>()?;
+    let codes = sources
+        .iter()
+        .zip(preprocessed.iter())
+        .map(|((_, path), shader)| to_c_string(path, &shader.code))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let mut files: Vec<PathBuf> = Vec::new();
+    for file in preprocessed.iter().flat_map(|shader| shader.files.iter()) {
+        if !files.contains(file) {
+            files.push(file.clone());
+        }
+    }
+
+    let cache = cache
+        .filter(|_| ProgramCache::is_supported())
+        .map(|cache| {
+            let stage_codes: Vec<_> = sources
+                .iter()
+                .zip(preprocessed.iter())
+                .map(|((stage, _), shader)| (*stage, shader.code.as_str()))
+                .collect();
+            (cache, cache.key(&stage_codes))
+        });
+    if let Some(shader_id) = cache.and_then(|(cache, key)| cache.load(key)) {
+        return Ok((shader_id, files));
+    }
 
-    match code {
-        Ok(src) => Some(src),
-        Err(_) => {
-            error!("Could not open shader source at {}", source);
-            None
+    let shader_id;
+    gl_call!(shader_id = gl::CreateProgram());
+    if let Some((cache, _)) = cache {
+        cache.prepare(shader_id);
+    }
+
+    let mut compiled_shaders = Vec::with_capacity(sources.len());
+    for (((stage, path), code), shader) in sources.iter().zip(codes.iter()).zip(preprocessed.iter()) {
+        match compile_shader(*stage, path, code, &shader.files) {
+            Ok(shader) => compiled_shaders.push(shader),
+            Err(error) => {
+                compiled_shaders
+                    .iter()
+                    .for_each(|shader| gl_call!(gl::DeleteShader(*shader)));
+                gl_call!(gl::DeleteProgram(shader_id));
+                return Err(error);
+            }
         }
     }
+
+    for shader in compiled_shaders.iter() {
+        gl_call!(gl::AttachShader(shader_id, *shader));
+    }
+    gl_call!(gl::LinkProgram(shader_id));
+    let link_result = check_program_error(shader_id);
+
+    for shader in compiled_shaders.iter() {
+        gl_call!(gl::DetachShader(shader_id, *shader));
+        gl_call!(gl::DeleteShader(*shader));
+    }
+
+    if let Err(error) = link_result {
+        gl_call!(gl::DeleteProgram(shader_id));
+        return Err(error);
+    }
+
+    if let Some((cache, key)) = cache {
+        cache.store(key, shader_id);
+    }
+
+    Ok((shader_id, files))
+}
+
+fn to_c_string(source: &Path, code: &str) -> Result<CString, ShaderError> {
+    CString::new(code).map_err(|error| {
+        ShaderError::io(source, io::Error::new(io::ErrorKind::InvalidData, error))
+    })
 }
 
-fn check_shader_error(shader_id: GLuint, shader_path: &Path) {
+//  `files` maps the source string numbers of the `#line` directives back to
+//  the original files.
+fn compile_shader(
+    stage: ShaderStage,
+    path: &Path,
+    code: &CString,
+    files: &[PathBuf],
+) -> Result<GLuint, ShaderError> {
+    let shader;
+    gl_call!(shader = gl::CreateShader(stage.gl_type()));
+    gl_call!(gl::ShaderSource(shader, 1, &code.as_ptr(), ptr::null()));
+    gl_call!(gl::CompileShader(shader));
+
     let mut result = 0;
-    gl_call!(gl::GetShaderiv(shader_id, gl::COMPILE_STATUS, &mut result));
+    gl_call!(gl::GetShaderiv(shader, gl::COMPILE_STATUS, &mut result));
     if result == 0 {
-        let mut info_log: Vec<u8> = Vec::with_capacity(1024);
         let mut info_len = 0;
-        unsafe {
-            info_log.set_len(1024);
-        }
+        gl_call!(gl::GetShaderiv(shader, gl::INFO_LOG_LENGTH, &mut info_len));
 
+        let mut info_log: Vec<u8> = vec![0; info_len.max(1) as usize];
         gl_call!(gl::GetShaderInfoLog(
-            shader_id,
-            1023,
+            shader,
+            info_log.len() as GLsizei,
             &mut info_len,
             info_log.as_mut_ptr() as *mut GLchar,
         ));
-        unsafe {
-            info_log.set_len(info_len as usize);
-        }
-
-        warn!(
-            "Could not compile shader {}:\n{}",
-            shader_path.as_os_str().to_str().unwrap(),
-            String::from_utf8_lossy(info_log.as_slice())
-        );
+        info_log.truncate(info_len as usize);
+        gl_call!(gl::DeleteShader(shader));
+
+        return Err(ShaderError::Compile {
+            stage,
+            path: path.to_path_buf(),
+            log: ShaderDiagnostic::parse_log(
+                &String::from_utf8_lossy(&info_log),
+                files,
+            ),
+        });
     }
+
+    Ok(shader)
 }
 
-fn check_program_error(program_id: GLuint, vertex_path: &Path, fragment_path: &Path) {
+fn check_program_error(program_id: GLuint) -> Result<(), ShaderError> {
     let mut result = 0;
     gl_call!(gl::GetProgramiv(program_id, gl::LINK_STATUS, &mut result));
     if result == 0 {
-        let mut info_log: Vec<u8> = Vec::with_capacity(1024);
         let mut info_len = 0;
-        unsafe {
-            info_log.set_len(1024);
-        }
+        gl_call!(gl::GetProgramiv(program_id, gl::INFO_LOG_LENGTH, &mut info_len));
 
+        let mut info_log: Vec<u8> = vec![0; info_len.max(1) as usize];
         gl_call!(gl::GetProgramInfoLog(
             program_id,
-            1023,
+            info_log.len() as GLsizei,
             &mut info_len,
             info_log.as_mut_ptr() as *mut GLchar,
         ));
-        unsafe {
-            info_log.set_len(info_len as usize);
-        }
+        info_log.truncate(info_len as usize);
 
-        warn!(
-            "Could not link shaders {} and {}:\n{}",
-            vertex_path.as_os_str().to_str().unwrap(),
-            fragment_path.as_os_str().to_str().unwrap(),
-            String::from_utf8_lossy(info_log.as_slice())
-        );
+        return Err(ShaderError::Link {
+            log: ShaderDiagnostic::parse_log(&String::from_utf8_lossy(&info_log), &[]),
+        });
     }
-}
 
-fn get_uniform_location(shader_id: GLuint, uniform_name: &str) -> GLint {
-    let uniform_location;
+    Ok(())
+}
 
-    gl_call!(uniform_location = gl::GetUniformLocation(shader_id, str_to_c_str_ptr!(uniform_name)));
-    if uniform_location == -1 {
-        log::info!(
-            "Could not find uniform {} in shader {}",
-            uniform_name,
+fn uniform_block_binding(shader_id: GLuint, block_name: &str, binding: GLuint) {
+    let block_index;
+    gl_call!(block_index = gl::GetUniformBlockIndex(shader_id, str_to_c_str_ptr!(block_name)));
+    if block_index == gl::INVALID_INDEX {
+        log::warn!(
+            "Could not find uniform block {} in shader {}",
+            block_name,
             shader_id
         );
+        return;
     }
 
-    uniform_location
-}
\ No newline at end of file
+    gl_call!(gl::UniformBlockBinding(shader_id, block_index, binding));
+}
+
+fn modification_times(files: &[PathBuf]) -> Vec<Option<SystemTime>> {
+    files
+        .iter()
+        .map(|path| fs::metadata(path).and_then(|metadata| metadata.modified()).ok())
+        .collect()
+}
+
+//  Reads every active uniform of `from` back and uploads it to the same name
+//  in `to`. Leaves `to` bound.
+fn copy_uniform_values(from: GLuint, to: GLuint) {
+    gl_call!(gl::UseProgram(to));
+
+    for uniform in active_uniforms(from) {
+        //  Arrays are reported as `name[0]` and are copied element by element.
+        let base_name = uniform.name.trim_end_matches("[0]");
+        let is_array = uniform.size > 1 || uniform.name.ends_with("[0]");
+
+        for element in 0..uniform.size {
+            let name = if is_array {
+                format!("{}[{}]", base_name, element)
+            } else {
+                base_name.to_string()
+            };
+
+            let from_location;
+            let to_location;
+            gl_call!(from_location = gl::GetUniformLocation(from, str_to_c_str_ptr!(name)));
+            gl_call!(to_location = gl::GetUniformLocation(to, str_to_c_str_ptr!(name)));
+            if from_location == -1 || to_location == -1 {
+                continue;
+            }
+
+            copy_uniform_value(uniform.gl_type, from, from_location, to_location);
+        }
+    }
+}
+
+fn copy_uniform_value(gl_type: GLenum, from: GLuint, from_location: GLint, to_location: GLint) {
+    let mut floats = [0.0f32; 16];
+    let mut ints = [0i32; 4];
+    let mut uints = [0u32; 4];
+    let floats_ptr = floats.as_mut_ptr();
+    let ints_ptr = ints.as_mut_ptr();
+    let uints_ptr = uints.as_mut_ptr();
+
+    match gl_type {
+        gl::FLOAT | gl::FLOAT_VEC2 | gl::FLOAT_VEC3 | gl::FLOAT_VEC4 => {
+            gl_call!(gl::GetUniformfv(from, from_location, floats_ptr));
+            match gl_type {
+                gl::FLOAT => gl_call!(gl::Uniform1fv(to_location, 1, floats_ptr)),
+                gl::FLOAT_VEC2 => gl_call!(gl::Uniform2fv(to_location, 1, floats_ptr)),
+                gl::FLOAT_VEC3 => gl_call!(gl::Uniform3fv(to_location, 1, floats_ptr)),
+                _ => gl_call!(gl::Uniform4fv(to_location, 1, floats_ptr)),
+            }
+        }
+        gl::FLOAT_MAT2 | gl::FLOAT_MAT3 | gl::FLOAT_MAT4 | gl::FLOAT_MAT2x3 | gl::FLOAT_MAT2x4
+        | gl::FLOAT_MAT3x2 | gl::FLOAT_MAT3x4 | gl::FLOAT_MAT4x2 | gl::FLOAT_MAT4x3 => {
+            gl_call!(gl::GetUniformfv(from, from_location, floats_ptr));
+            match gl_type {
+                gl::FLOAT_MAT2 => gl_call!(gl::UniformMatrix2fv(to_location, 1, gl::FALSE, floats_ptr)),
+                gl::FLOAT_MAT3 => gl_call!(gl::UniformMatrix3fv(to_location, 1, gl::FALSE, floats_ptr)),
+                gl::FLOAT_MAT4 => gl_call!(gl::UniformMatrix4fv(to_location, 1, gl::FALSE, floats_ptr)),
+                gl::FLOAT_MAT2x3 => gl_call!(gl::UniformMatrix2x3fv(to_location, 1, gl::FALSE, floats_ptr)),
+                gl::FLOAT_MAT2x4 => gl_call!(gl::UniformMatrix2x4fv(to_location, 1, gl::FALSE, floats_ptr)),
+                gl::FLOAT_MAT3x2 => gl_call!(gl::UniformMatrix3x2fv(to_location, 1, gl::FALSE, floats_ptr)),
+                gl::FLOAT_MAT3x4 => gl_call!(gl::UniformMatrix3x4fv(to_location, 1, gl::FALSE, floats_ptr)),
+                gl::FLOAT_MAT4x2 => gl_call!(gl::UniformMatrix4x2fv(to_location, 1, gl::FALSE, floats_ptr)),
+                _ => gl_call!(gl::UniformMatrix4x3fv(to_location, 1, gl::FALSE, floats_ptr)),
+            }
+        }
+        gl::UNSIGNED_INT | gl::UNSIGNED_INT_VEC2 | gl::UNSIGNED_INT_VEC3 | gl::UNSIGNED_INT_VEC4 => {
+            gl_call!(gl::GetUniformuiv(from, from_location, uints_ptr));
+            match gl_type {
+                gl::UNSIGNED_INT => gl_call!(gl::Uniform1uiv(to_location, 1, uints_ptr)),
+                gl::UNSIGNED_INT_VEC2 => gl_call!(gl::Uniform2uiv(to_location, 1, uints_ptr)),
+                gl::UNSIGNED_INT_VEC3 => gl_call!(gl::Uniform3uiv(to_location, 1, uints_ptr)),
+                _ => gl_call!(gl::Uniform4uiv(to_location, 1, uints_ptr)),
+            }
+        }
+        gl::INT_VEC2 | gl::BOOL_VEC2 => {
+            gl_call!(gl::GetUniformiv(from, from_location, ints_ptr));
+            gl_call!(gl::Uniform2iv(to_location, 1, ints_ptr));
+        }
+        gl::INT_VEC3 | gl::BOOL_VEC3 => {
+            gl_call!(gl::GetUniformiv(from, from_location, ints_ptr));
+            gl_call!(gl::Uniform3iv(to_location, 1, ints_ptr));
+        }
+        gl::INT_VEC4 | gl::BOOL_VEC4 => {
+            gl_call!(gl::GetUniformiv(from, from_location, ints_ptr));
+            gl_call!(gl::Uniform4iv(to_location, 1, ints_ptr));
+        }
+        gl::DOUBLE
+        | gl::DOUBLE_VEC2
+        | gl::DOUBLE_VEC3
+        | gl::DOUBLE_VEC4
+        | gl::DOUBLE_MAT2
+        | gl::DOUBLE_MAT3
+        | gl::DOUBLE_MAT4 => {
+            log::warn!("Double uniforms are not carried over on reload");
+        }
+        //  int, bool and every sampler/image type.
+        _ => {
+            gl_call!(gl::GetUniformiv(from, from_location, ints_ptr));
+            gl_call!(gl::Uniform1iv(to_location, 1, ints_ptr));
+        }
+    }
+}
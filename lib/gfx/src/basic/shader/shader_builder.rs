@@ -0,0 +1,173 @@
+use super::{ComputeProgram, ProgramCache, Shader, ShaderError, ShaderPreprocessor, ShaderStage};
+use crate::GlContext;
+use std::path::{Path, PathBuf};
+
+/// Builds a program from any valid set of stages: a vertex shader with
+/// optional tessellation, geometry and fragment stages, or a lone compute
+/// shader.
+#[derive(Clone, Debug, Default)]
+pub struct ShaderBuilder {
+    sources: Vec<(ShaderStage, PathBuf)>,
+    preprocessor: ShaderPreprocessor,
+    cache: Option<ProgramCache>,
+}
+
+impl ShaderBuilder {
+    pub fn new() -> ShaderBuilder {
+        ShaderBuilder::default()
+    }
+
+    pub fn stage(mut self, stage: ShaderStage, source: &Path) -> ShaderBuilder {
+        self.sources.push((stage, source.to_path_buf()));
+        self
+    }
+
+    pub fn vertex(self, source: &Path) -> ShaderBuilder {
+        self.stage(ShaderStage::Vertex, source)
+    }
+
+    pub fn tess_control(self, source: &Path) -> ShaderBuilder {
+        self.stage(ShaderStage::TessControl, source)
+    }
+
+    pub fn tess_evaluation(self, source: &Path) -> ShaderBuilder {
+        self.stage(ShaderStage::TessEvaluation, source)
+    }
+
+    pub fn geometry(self, source: &Path) -> ShaderBuilder {
+        self.stage(ShaderStage::Geometry, source)
+    }
+
+    pub fn fragment(self, source: &Path) -> ShaderBuilder {
+        self.stage(ShaderStage::Fragment, source)
+    }
+
+    pub fn compute(self, source: &Path) -> ShaderBuilder {
+        self.stage(ShaderStage::Compute, source)
+    }
+
+    pub fn define(mut self, name: &str, value: &str) -> ShaderBuilder {
+        self.preprocessor = self.preprocessor.define(name, value);
+        self
+    }
+
+    pub fn preprocessor(mut self, preprocessor: ShaderPreprocessor) -> ShaderBuilder {
+        self.preprocessor = preprocessor;
+        self
+    }
+
+    /// Loads the linked program from `cache` when possible and stores it
+    /// there otherwise. Reloads go through the cache too.
+    pub fn cache(mut self, cache: ProgramCache) -> ShaderBuilder {
+        self.cache = Some(cache);
+        self
+    }
+
+    /// Builds a graphics program. Fails with `ShaderError::InvalidStages` for
+    /// compute shaders, use `build_compute` for those.
+    pub fn build(self, _context: &GlContext) -> Result<Shader, ShaderError> {
+        self.check_stages()?;
+        if self.has_stage(ShaderStage::Compute) {
+            return Err(ShaderError::InvalidStages(
+                "compute shaders have to be built with build_compute".to_string(),
+            ));
+        }
+
+        Shader::link(self.sources, self.preprocessor, self.cache)
+    }
+
+    pub fn build_compute(self, _context: &GlContext) -> Result<ComputeProgram, ShaderError> {
+        self.check_stages()?;
+        if !self.has_stage(ShaderStage::Compute) {
+            return Err(ShaderError::InvalidStages(
+                "a compute program needs a compute stage".to_string(),
+            ));
+        }
+
+        Shader::link(self.sources, self.preprocessor, self.cache).map(ComputeProgram::from_shader)
+    }
+
+    fn has_stage(&self, stage: ShaderStage) -> bool {
+        self.sources.iter().any(|(source_stage, _)| *source_stage == stage)
+    }
+
+    fn check_stages(&self) -> Result<(), ShaderError> {
+        let invalid = |message: &str| Err(ShaderError::InvalidStages(message.to_string()));
+
+        if self.sources.is_empty() {
+            return invalid("no stages were given");
+        }
+        for (index, (stage, _)) in self.sources.iter().enumerate() {
+            if self.sources[..index].iter().any(|(other, _)| other == stage) {
+                return invalid(&format!("the {} stage was given more than once", stage));
+            }
+        }
+
+        if self.has_stage(ShaderStage::Compute) {
+            if self.sources.len() > 1 {
+                return invalid("a compute shader can not be linked with other stages");
+            }
+            return Ok(());
+        }
+
+        if !self.has_stage(ShaderStage::Vertex) {
+            return invalid("a graphics program needs a vertex stage");
+        }
+        if self.has_stage(ShaderStage::TessControl) && !self.has_stage(ShaderStage::TessEvaluation)
+        {
+            return invalid("a tessellation control stage needs a tessellation evaluation stage");
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn builder(stages: &[ShaderStage]) -> ShaderBuilder {
+        stages
+            .iter()
+            .fold(ShaderBuilder::new(), |builder, stage| builder.stage(*stage, Path::new("shader.glsl")))
+    }
+
+    #[test]
+    fn check_stages_rejects_invalid_combinations() {
+        let cases: &[&[ShaderStage]] = &[
+            &[],
+            &[ShaderStage::Fragment],
+            &[ShaderStage::Vertex, ShaderStage::Fragment, ShaderStage::Vertex],
+            &[ShaderStage::Compute, ShaderStage::Vertex],
+            &[ShaderStage::Vertex, ShaderStage::Compute, ShaderStage::Fragment],
+            &[ShaderStage::Vertex, ShaderStage::TessControl, ShaderStage::Fragment],
+        ];
+
+        for stages in cases {
+            assert!(
+                matches!(builder(stages).check_stages(), Err(ShaderError::InvalidStages(_))),
+                "{:?} was accepted",
+                stages
+            );
+        }
+    }
+
+    #[test]
+    fn check_stages_accepts_valid_combinations() {
+        let cases: &[&[ShaderStage]] = &[
+            &[ShaderStage::Vertex],
+            &[
+                ShaderStage::Vertex,
+                ShaderStage::TessControl,
+                ShaderStage::TessEvaluation,
+                ShaderStage::Geometry,
+                ShaderStage::Fragment,
+            ],
+            &[ShaderStage::Compute],
+        ];
+
+        for stages in cases {
+            assert!(builder(stages).check_stages().is_ok(), "{:?} was rejected", stages);
+        }
+    }
+}
@@ -0,0 +1,233 @@
+use super::ShaderStage;
+use std::{
+    error::Error,
+    fmt, io,
+    path::{Path, PathBuf},
+};
+
+#[derive(Debug)]
+pub enum ShaderError {
+    Io {
+        path: PathBuf,
+        error: io::Error,
+    },
+    Compile {
+        stage: ShaderStage,
+        path: PathBuf,
+        log: Vec<ShaderDiagnostic>,
+    },
+    Link {
+        log: Vec<ShaderDiagnostic>,
+    },
+    IncludeCycle {
+        chain: Vec<PathBuf>,
+    },
+    InvalidDirective {
+        path: PathBuf,
+        line: u32,
+        message: String,
+    },
+    InvalidStages(String),
+}
+
+/// A single message of a driver info log. `file` and `line` are only known
+/// when the driver reported them in a format we understand.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ShaderDiagnostic {
+    pub file: Option<PathBuf>,
+    pub line: Option<u32>,
+    pub message: String,
+}
+
+impl ShaderDiagnostic {
+    /// Parses a driver info log. `files` maps the source string numbers used
+    /// by the driver (the first number of `0:12(5)` or `0(12)`) to paths.
+    pub fn parse_log(log: &str, files: &[PathBuf]) -> Vec<ShaderDiagnostic> {
+        log.lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty())
+            .map(|line| match parse_location(line) {
+                Some((source, line_number, message)) => ShaderDiagnostic {
+                    file: files.get(source as usize).cloned(),
+                    line: Some(line_number),
+                    message: message.to_string(),
+                },
+                None => ShaderDiagnostic {
+                    file: None,
+                    line: None,
+                    message: line.to_string(),
+                },
+            })
+            .collect()
+    }
+}
+
+impl fmt::Display for ShaderDiagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match (&self.file, self.line) {
+            (Some(file), Some(line)) => write!(f, "{}:{}: {}", file.display(), line, self.message),
+            (None, Some(line)) => write!(f, "<unknown>:{}: {}", line, self.message),
+            _ => write!(f, "{}", self.message),
+        }
+    }
+}
+
+impl fmt::Display for ShaderError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ShaderError::Io { path, error } => {
+                write!(f, "Could not open shader source at {}: {}", path.display(), error)
+            }
+            ShaderError::Compile { stage, path, log } => {
+                write!(f, "Could not compile {} shader {}:", stage, path.display())?;
+                write_log(f, log)
+            }
+            ShaderError::Link { log } => {
+                write!(f, "Could not link shader program:")?;
+                write_log(f, log)
+            }
+            ShaderError::IncludeCycle { chain } => {
+                write!(f, "Include cycle detected:")?;
+                for path in chain {
+                    write!(f, "\n    {}", path.display())?;
+                }
+
+                Ok(())
+            }
+            ShaderError::InvalidDirective {
+                path,
+                line,
+                message,
+            } => write!(f, "{}:{}: {}", path.display(), line, message),
+            ShaderError::InvalidStages(message) => {
+                write!(f, "Invalid set of shader stages: {}", message)
+            }
+        }
+    }
+}
+
+impl Error for ShaderError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            ShaderError::Io { error, .. } => Some(error),
+            _ => None,
+        }
+    }
+}
+
+impl ShaderError {
+    pub(crate) fn io(path: &Path, error: io::Error) -> ShaderError {
+        ShaderError::Io {
+            path: path.to_path_buf(),
+            error,
+        }
+    }
+}
+
+fn write_log(f: &mut fmt::Formatter<'_>, log: &[ShaderDiagnostic]) -> fmt::Result {
+    for diagnostic in log {
+        write!(f, "\n    {}", diagnostic)?;
+    }
+
+    Ok(())
+}
+
+//  Understands the common driver formats:
+//      Mesa:       0:12(5): error: ...
+//      NVIDIA:     0(12) : error C0000: ...
+//      AMD/Intel:  ERROR: 0:12: ...
+fn parse_location(line: &str) -> Option<(u32, u32, &str)> {
+    let rest = ["ERROR:", "WARNING:"]
+        .iter()
+        .find_map(|prefix| line.strip_prefix(prefix))
+        .map(str::trim_start)
+        .unwrap_or(line);
+
+    let (source, rest) = split_number(rest)?;
+    let (line_number, rest) = if let Some(rest) = rest.strip_prefix(':') {
+        split_number(rest)?
+    } else {
+        let (line_number, rest) = split_number(rest.strip_prefix('(')?)?;
+        (line_number, rest.strip_prefix(')')?)
+    };
+
+    //  Skip the optional column number.
+    let rest = match rest.strip_prefix('(').and_then(split_number) {
+        Some((_, column_rest)) => column_rest.strip_prefix(')').unwrap_or(column_rest),
+        None => rest,
+    };
+    let message = rest.trim_start().strip_prefix(':')?.trim();
+
+    Some((source, line_number, message))
+}
+
+fn split_number(text: &str) -> Option<(u32, &str)> {
+    let end = text
+        .find(|c: char| !c.is_ascii_digit())
+        .unwrap_or(text.len());
+    let number = text[..end].parse().ok()?;
+
+    Some((number, &text[end..]))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_log_understands_the_driver_formats() {
+        let files = [PathBuf::from("basic.vs"), PathBuf::from("include/matrices.glsl")];
+        let cases = [
+            (
+                "0:12(5): error: `aPos' undeclared",
+                Some(0),
+                Some(12),
+                "error: `aPos' undeclared",
+            ),
+            (
+                "1(12) : error C0000: syntax error, unexpected '}'",
+                Some(1),
+                Some(12),
+                "error C0000: syntax error, unexpected '}'",
+            ),
+            (
+                "ERROR: 0:12: 'aPos' : undeclared identifier",
+                Some(0),
+                Some(12),
+                "'aPos' : undeclared identifier",
+            ),
+            //  A source string number without a known file.
+            ("7:3(1): warning: unused", None, Some(3), "warning: unused"),
+            (
+                "Vertex shader failed to compile with the following errors:",
+                None,
+                None,
+                "Vertex shader failed to compile with the following errors:",
+            ),
+        ];
+
+        for (line, file, line_number, message) in cases.iter() {
+            let diagnostics = ShaderDiagnostic::parse_log(line, &files);
+            assert_eq!(
+                diagnostics,
+                [ShaderDiagnostic {
+                    file: file.map(|index: usize| files[index].clone()),
+                    line: *line_number,
+                    message: message.to_string(),
+                }],
+                "parsing {:?}",
+                line
+            );
+        }
+    }
+
+    #[test]
+    fn parse_log_skips_empty_lines() {
+        let diagnostics = ShaderDiagnostic::parse_log("\n0:1(1): error: a\n\n  \n0:2(1): error: b\n", &[]);
+
+        assert_eq!(
+            diagnostics.iter().map(|diagnostic| diagnostic.line).collect::<Vec<_>>(),
+            [Some(1), Some(2)]
+        );
+    }
+}
@@ -0,0 +1,150 @@
+use super::{Shader, ShaderBuilder, ShaderError, ShaderPreprocessor};
+use crate::GlContext;
+use std::{
+    borrow::Cow,
+    collections::HashMap,
+    fs, io,
+    path::{Component, Path, PathBuf},
+    sync::Arc,
+};
+
+/// Where shader sources (and the files they include) are read from: a
+/// directory on disk, or files kept in memory such as the ones embedded by
+/// `shader_files!`. Paths are relative to `root` in both cases.
+#[derive(Clone, Debug, Default)]
+pub struct ShaderFiles {
+    root: PathBuf,
+    memory: Option<Arc<HashMap<PathBuf, Cow<'static, str>>>>,
+}
+
+impl ShaderFiles {
+    pub fn on_disk<P: Into<PathBuf>>(root: P) -> ShaderFiles {
+        ShaderFiles {
+            root: root.into(),
+            memory: None,
+        }
+    }
+
+    /// Reads from `directory`, looked up at runtime relative to the working
+    /// directory first and then relative to the directory of the executable
+    /// and its parents (so a binary in `target/release` finds the `res`
+    /// folder next to `Cargo.toml`). Falls back to `directory` as it is if
+    /// none of them has it.
+    pub fn locate<P: AsRef<Path>>(directory: P) -> ShaderFiles {
+        let directory = directory.as_ref();
+        if directory.is_dir() {
+            return ShaderFiles::on_disk(directory);
+        }
+
+        let executable = std::env::current_exe().ok();
+        let next_to_executable = executable
+            .iter()
+            .flat_map(|executable| executable.ancestors().skip(1))
+            .map(|ancestor| ancestor.join(directory))
+            .find(|candidate| candidate.is_dir());
+
+        ShaderFiles::on_disk(next_to_executable.unwrap_or_else(|| directory.to_path_buf()))
+    }
+
+    /// `files` are `(path relative to root, content)` pairs.
+    pub fn embedded<P: Into<PathBuf>>(
+        root: P,
+        files: &'static [(&'static str, &'static str)],
+    ) -> ShaderFiles {
+        let root = root.into();
+        let files = files
+            .iter()
+            .map(|(path, code)| (normalize(&root.join(path)), Cow::Borrowed(*code)))
+            .collect();
+
+        ShaderFiles {
+            root,
+            memory: Some(Arc::new(files)),
+        }
+    }
+
+    pub fn in_memory(files: Vec<(PathBuf, String)>) -> ShaderFiles {
+        let files = files
+            .into_iter()
+            .map(|(path, code)| (normalize(&path), Cow::Owned(code)))
+            .collect();
+
+        ShaderFiles {
+            root: PathBuf::new(),
+            memory: Some(Arc::new(files)),
+        }
+    }
+
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    pub fn is_in_memory(&self) -> bool {
+        self.memory.is_some()
+    }
+
+    /// A `ShaderBuilder` reading its stages from these files. Stage paths are
+    /// relative to `root`.
+    pub fn builder(&self) -> ShaderBuilder {
+        ShaderBuilder::new().preprocessor(ShaderPreprocessor::new().files(self.clone()))
+    }
+
+    pub fn shader(
+        &self,
+        context: &GlContext,
+        vertex_source: &Path,
+        fragment_source: &Path,
+    ) -> Result<Shader, ShaderError> {
+        self.builder().vertex(vertex_source).fragment(fragment_source).build(context)
+    }
+
+    pub(crate) fn resolve(&self, path: &Path) -> PathBuf {
+        self.root.join(path)
+    }
+
+    pub(crate) fn read(&self, path: &Path) -> Result<String, ShaderError> {
+        match &self.memory {
+            Some(files) => files
+                .get(&normalize(path))
+                .map(|code| code.to_string())
+                .ok_or_else(|| {
+                    ShaderError::io(
+                        path,
+                        io::Error::new(io::ErrorKind::NotFound, "not an embedded shader file"),
+                    )
+                }),
+            None => fs::read_to_string(path).map_err(|error| ShaderError::io(path, error)),
+        }
+    }
+}
+
+//  Resolves `.` and `..` without touching the file system, so includes like
+//  "../common.glsl" can be looked up in memory.
+fn normalize(path: &Path) -> PathBuf {
+    let mut normalized = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => {
+                if !normalized.pop() {
+                    normalized.push("..");
+                }
+            }
+            component => normalized.push(component),
+        }
+    }
+
+    normalized
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn locate_prefers_the_working_directory() {
+        //  Tests run in the crate's directory.
+        assert_eq!(ShaderFiles::locate("src").root(), Path::new("src"));
+        assert_eq!(ShaderFiles::locate("missing").root(), Path::new("missing"));
+    }
+}
@@ -1,9 +1,16 @@
 use gl::types::GLint;
 use crate::prelude::{Bindable};
-use super::ShaderUniform;
+use super::{ShaderUniform, UniformInfo};
+use std::{any::TypeId, rc::Rc};
 
 
 pub trait ShaderObject: Bindable {
     fn set_uniform<T: ShaderUniform>(&mut self, uniform_name: &str, value: T);
     fn get_uniform_location(&mut self, uniform_name: &str) -> GLint;
-}
\ No newline at end of file
+    fn uniform_info(&self, uniform_name: &str) -> Option<&UniformInfo>;
+
+    /// The locations `Uniforms::locate` found for the type `key` in this
+    /// program. Forgotten when the program is reloaded.
+    fn cached_uniform_locations(&self, key: TypeId) -> Option<Rc<[GLint]>>;
+    fn cache_uniform_locations(&mut self, key: TypeId, locations: Rc<[GLint]>);
+}
@@ -0,0 +1,336 @@
+use super::{ShaderError, ShaderFiles};
+use std::path::{Path, PathBuf};
+
+/// Resolves `#include "file"` directives and injects `#define`s right after
+/// `#version`. Includes are relative to the root of `files`, or to the
+/// directory of the processed stage file when `files` has no root (as for
+/// `Shader::new`). Either way they are not relative to the including file, so
+/// every file names a shared include the same way.
+/// Directives inside `//` and `/* */` comments are left alone. `#line` directives are emitted
+/// around every include, so driver messages can be mapped back to the
+/// original file and line through `PreprocessedShader::files`.
+#[derive(Clone, Debug, Default)]
+pub struct ShaderPreprocessor {
+    defines: Vec<(String, String)>,
+    files: ShaderFiles,
+}
+
+#[derive(Clone, Debug)]
+pub struct PreprocessedShader {
+    pub code: String,
+    /// Every file that ended up in `code`. The index of a file is the source
+    /// string number used in its `#line` directives.
+    pub files: Vec<PathBuf>,
+}
+
+impl ShaderPreprocessor {
+    pub fn new() -> ShaderPreprocessor {
+        ShaderPreprocessor::default()
+    }
+
+    pub fn define(mut self, name: &str, value: &str) -> ShaderPreprocessor {
+        self.defines.push((name.to_string(), value.to_string()));
+        self
+    }
+
+    pub fn defines(&self) -> &[(String, String)] {
+        &self.defines
+    }
+
+    /// Reads sources and includes from `files` instead of relative to the
+    /// working directory.
+    pub fn files(mut self, files: ShaderFiles) -> ShaderPreprocessor {
+        self.files = files;
+        self
+    }
+
+    pub fn process(&self, path: &Path) -> Result<PreprocessedShader, ShaderError> {
+        let mut output = PreprocessedShader {
+            code: String::new(),
+            files: Vec::new(),
+        };
+        let mut include_stack = Vec::new();
+
+        let path = self.files.resolve(path);
+        let include_root = if self.files.root().as_os_str().is_empty() {
+            path.parent().map(Path::to_path_buf).unwrap_or_default()
+        } else {
+            self.files.root().to_path_buf()
+        };
+        self.process_file(&path, &include_root, &mut output, &mut include_stack, true)?;
+
+        Ok(output)
+    }
+
+    fn process_file(
+        &self,
+        path: &Path,
+        include_root: &Path,
+        output: &mut PreprocessedShader,
+        include_stack: &mut Vec<PathBuf>,
+        is_root: bool,
+    ) -> Result<(), ShaderError> {
+        let code = self.files.read(path)?;
+
+        //  Compare canonical paths, so "a/../b.glsl" and "b.glsl" are the same
+        //  file when looking for cycles.
+        let canonical_path = path.canonicalize().unwrap_or_else(|_| path.to_path_buf());
+        if include_stack.contains(&canonical_path) {
+            let mut chain = include_stack.clone();
+            chain.push(canonical_path);
+            return Err(ShaderError::IncludeCycle { chain });
+        }
+        include_stack.push(canonical_path);
+
+        let file_index = match output.files.iter().position(|file| file == path) {
+            Some(index) => index,
+            None => {
+                output.files.push(path.to_path_buf());
+                output.files.len() - 1
+            }
+        };
+
+        let has_version = is_root && {
+            let mut in_comment = false;
+            code.lines()
+                .any(|line| is_version_directive(&strip_comments(line, &mut in_comment)))
+        };
+        if is_root && !has_version {
+            self.write_defines(output, 1, file_index);
+        }
+
+        let mut in_comment = false;
+        for (index, line) in code.lines().enumerate() {
+            let line_number = index + 1;
+            let stripped = strip_comments(line, &mut in_comment);
+            let directive = stripped.trim_start();
+
+            if is_version_directive(directive) {
+                if is_root {
+                    output.code.push_str(line);
+                    output.code.push('\n');
+                    self.write_defines(output, line_number + 1, file_index);
+                } else {
+                    //  Keep the line count intact.
+                    output.code.push_str("// ");
+                    output.code.push_str(line);
+                    output.code.push('\n');
+                }
+            } else if let Some(arguments) = directive.strip_prefix("#include") {
+                let include_name = parse_include(arguments).ok_or_else(|| {
+                    ShaderError::InvalidDirective {
+                        path: path.to_path_buf(),
+                        line: line_number as u32,
+                        message: format!("expected #include \"file\", found {}", line.trim()),
+                    }
+                })?;
+                let include_path = include_root.join(include_name);
+
+                let include_index = output
+                    .files
+                    .iter()
+                    .position(|file| *file == include_path)
+                    .unwrap_or(output.files.len());
+                output.code += &format!("#line 1 {}\n", include_index);
+                self.process_file(&include_path, include_root, output, include_stack, false)?;
+                output.code += &format!("#line {} {}\n", line_number + 1, file_index);
+            } else {
+                output.code.push_str(line);
+                output.code.push('\n');
+            }
+        }
+
+        include_stack.pop();
+        Ok(())
+    }
+
+    fn write_defines(&self, output: &mut PreprocessedShader, next_line: usize, file_index: usize) {
+        if self.defines.is_empty() {
+            return;
+        }
+
+        for (name, value) in self.defines.iter() {
+            output.code += &format!("#define {} {}\n", name, value);
+        }
+        output.code += &format!("#line {} {}\n", next_line, file_index);
+    }
+}
+
+fn is_version_directive(line: &str) -> bool {
+    line.trim_start().starts_with("#version")
+}
+
+//  The code of `line` with comments blanked out. `in_comment` carries an
+//  unterminated `/* */` comment over to the next line.
+fn strip_comments(line: &str, in_comment: &mut bool) -> String {
+    let mut code = String::with_capacity(line.len());
+    let mut rest = line;
+
+    loop {
+        if *in_comment {
+            match rest.find("*/") {
+                Some(end) => {
+                    *in_comment = false;
+                    code.push(' ');
+                    rest = &rest[end + 2..];
+                }
+                None => return code,
+            }
+        } else {
+            let line_comment = rest.find("//");
+            let block_comment = rest.find("/*");
+            match (line_comment, block_comment) {
+                (Some(start), block) if block.is_none_or(|block| start < block) => {
+                    code.push_str(&rest[..start]);
+                    return code;
+                }
+                (_, Some(start)) => {
+                    code.push_str(&rest[..start]);
+                    *in_comment = true;
+                    rest = &rest[start + 2..];
+                }
+                _ => {
+                    code.push_str(rest);
+                    return code;
+                }
+            }
+        }
+    }
+}
+
+fn parse_include(arguments: &str) -> Option<&str> {
+    let arguments = arguments.trim().strip_prefix('"')?;
+    let end = arguments.find('"')?;
+    let (name, rest) = (&arguments[..end], arguments[end + 1..].trim());
+
+    if name.is_empty() || !rest.is_empty() {
+        None
+    } else {
+        Some(name)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn files(files: &[(&str, &str)]) -> ShaderFiles {
+        ShaderFiles::in_memory(
+            files
+                .iter()
+                .map(|(path, code)| (PathBuf::from(path), code.to_string()))
+                .collect(),
+        )
+    }
+
+    #[test]
+    fn includes_are_relative_to_the_root() {
+        let files = files(&[
+            ("main.vs", "#include \"include/a.glsl\"\n"),
+            ("include/a.glsl", "#include \"include/b.glsl\"\n"),
+            ("include/b.glsl", "float b;\n"),
+        ]);
+        let shader = ShaderPreprocessor::new().files(files).process(Path::new("main.vs")).unwrap();
+
+        assert_eq!(
+            shader.files,
+            vec![
+                PathBuf::from("main.vs"),
+                PathBuf::from("include/a.glsl"),
+                PathBuf::from("include/b.glsl"),
+            ]
+        );
+        assert!(shader.code.contains("float b;\n"));
+    }
+
+    #[test]
+    fn includes_on_disk_are_relative_to_the_stage_directory() {
+        let directory = std::env::temp_dir().join(format!("gfx-preprocessor-{}", std::process::id()));
+        let shaders = directory.join("shaders");
+        std::fs::create_dir_all(shaders.join("include")).unwrap();
+        std::fs::write(shaders.join("basic.vs"), "#version 330 core\n#include \"include/a.glsl\"\n").unwrap();
+        std::fs::write(shaders.join("include/a.glsl"), "#include \"include/b.glsl\"\n").unwrap();
+        std::fs::write(shaders.join("include/b.glsl"), "float b;\n").unwrap();
+
+        let result = ShaderPreprocessor::new().process(&shaders.join("basic.vs"));
+        std::fs::remove_dir_all(&directory).unwrap();
+
+        let shader = result.unwrap();
+        assert_eq!(
+            shader.files,
+            vec![
+                shaders.join("basic.vs"),
+                shaders.join("include/a.glsl"),
+                shaders.join("include/b.glsl"),
+            ]
+        );
+        assert!(shader.code.contains("float b;\n"));
+    }
+
+    #[test]
+    fn include_cycles_are_errors() {
+        let files = files(&[
+            ("main.vs", "#include \"a.glsl\"\n"),
+            ("a.glsl", "#include \"b.glsl\"\n"),
+            ("b.glsl", "#include \"a.glsl\"\n"),
+        ]);
+        let result = ShaderPreprocessor::new().files(files).process(Path::new("main.vs"));
+
+        match result {
+            Err(ShaderError::IncludeCycle { chain }) => assert_eq!(
+                chain,
+                vec![
+                    PathBuf::from("main.vs"),
+                    PathBuf::from("a.glsl"),
+                    PathBuf::from("b.glsl"),
+                    PathBuf::from("a.glsl"),
+                ]
+            ),
+            other => panic!("expected an include cycle, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn defines_follow_the_version() {
+        let files = files(&[("main.vs", "#version 330 core\nvoid main() {}\n")]);
+        let shader = ShaderPreprocessor::new()
+            .define("MAX_LIGHTS", "4")
+            .files(files)
+            .process(Path::new("main.vs"))
+            .unwrap();
+
+        assert_eq!(
+            shader.code,
+            "#version 330 core\n#define MAX_LIGHTS 4\n#line 2 0\nvoid main() {}\n"
+        );
+    }
+
+    #[test]
+    fn line_directives_map_back_to_the_files() {
+        let files = files(&[
+            ("main.vs", "#version 330 core\n#include \"a.glsl\"\nvoid main() {}\n"),
+            ("a.glsl", "float a;\n"),
+        ]);
+        let shader = ShaderPreprocessor::new().files(files).process(Path::new("main.vs")).unwrap();
+
+        assert_eq!(
+            shader.code,
+            "#version 330 core\n#line 1 1\nfloat a;\n#line 3 0\nvoid main() {}\n"
+        );
+    }
+
+    #[test]
+    fn commented_directives_are_ignored() {
+        let files = files(&[(
+            "main.vs",
+            "#version 330 core\n// #include \"a.glsl\"\n/*\n#include \"a.glsl\"\n*/\n",
+        )]);
+        let shader = ShaderPreprocessor::new().files(files).process(Path::new("main.vs")).unwrap();
+
+        assert_eq!(shader.files, vec![PathBuf::from("main.vs")]);
+        assert_eq!(
+            shader.code,
+            "#version 330 core\n// #include \"a.glsl\"\n/*\n#include \"a.glsl\"\n*/\n"
+        );
+    }
+}
@@ -0,0 +1,300 @@
+use gl::types::*;
+use crate::gl_call;
+use std::collections::HashMap;
+use utils::str_to_c_str_ptr;
+
+/// An active uniform of a linked program. Uniforms inside a uniform block have
+/// a `location` of -1.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct UniformInfo {
+    pub name: String,
+    pub gl_type: GLenum,
+    /// The array length, 1 for non-arrays.
+    pub size: GLint,
+    pub location: GLint,
+}
+
+/// An active vertex attribute of a linked program.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct AttributeInfo {
+    pub name: String,
+    pub gl_type: GLenum,
+    pub size: GLint,
+    pub location: GLint,
+}
+
+/// The active uniforms and attributes of a program, queried once after
+/// linking. Arrays can be looked up by their name (`lights`), their first
+/// element (`lights[0]`) and every other element (`lights[3]`).
+#[derive(Clone, Debug, Default)]
+pub struct ShaderReflection {
+    uniforms: HashMap<String, UniformInfo>,
+    attributes: HashMap<String, AttributeInfo>,
+}
+
+impl ShaderReflection {
+    pub fn new(program_id: GLuint) -> ShaderReflection {
+        let mut uniforms = HashMap::new();
+        for uniform in active_uniforms(program_id) {
+            let base_name = uniform.name.trim_end_matches("[0]").to_string();
+            let is_array = uniform.size > 1 || uniform.name.ends_with("[0]");
+
+            let location = uniform_location(program_id, &uniform.name);
+            let info = UniformInfo {
+                name: base_name.clone(),
+                gl_type: uniform.gl_type,
+                size: uniform.size,
+                location,
+            };
+
+            if is_array {
+                //  Element locations are not guaranteed to be consecutive.
+                for element in 1..uniform.size {
+                    let name = format!("{}[{}]", base_name, element);
+                    let location = uniform_location(program_id, &name);
+                    uniforms.insert(
+                        name.clone(),
+                        UniformInfo {
+                            name,
+                            gl_type: uniform.gl_type,
+                            size: 1,
+                            location,
+                        },
+                    );
+                }
+                uniforms.insert(
+                    format!("{}[0]", base_name),
+                    UniformInfo {
+                        name: format!("{}[0]", base_name),
+                        size: 1,
+                        ..info.clone()
+                    },
+                );
+            }
+            uniforms.insert(base_name, info);
+        }
+
+        let attributes = active_attributes(program_id)
+            .into_iter()
+            .map(|attribute| {
+                let location;
+                gl_call!(
+                    location = gl::GetAttribLocation(program_id, str_to_c_str_ptr!(attribute.name))
+                );
+
+                let info = AttributeInfo {
+                    name: attribute.name.trim_end_matches("[0]").to_string(),
+                    gl_type: attribute.gl_type,
+                    size: attribute.size,
+                    location,
+                };
+                (info.name.clone(), info)
+            })
+            .collect();
+
+        ShaderReflection {
+            uniforms,
+            attributes,
+        }
+    }
+
+    #[cfg(test)]
+    pub(crate) fn with_attributes(attributes: Vec<AttributeInfo>) -> ShaderReflection {
+        ShaderReflection {
+            uniforms: HashMap::new(),
+            attributes: attributes
+                .into_iter()
+                .map(|attribute| (attribute.name.clone(), attribute))
+                .collect(),
+        }
+    }
+
+    pub fn uniform(&self, name: &str) -> Option<&UniformInfo> {
+        self.uniforms.get(name)
+    }
+
+    pub fn attribute(&self, name: &str) -> Option<&AttributeInfo> {
+        self.attributes.get(name)
+    }
+
+    /// Every uniform, arrays only once under their base name.
+    pub fn uniforms(&self) -> impl Iterator<Item = &UniformInfo> {
+        self.uniforms
+            .iter()
+            .filter(|(key, _)| !key.ends_with(']'))
+            .map(|(_, info)| info)
+    }
+
+    pub fn attributes(&self) -> impl Iterator<Item = &AttributeInfo> {
+        self.attributes.values()
+    }
+}
+
+pub(crate) struct ActiveUniform {
+    pub name: String,
+    pub gl_type: GLenum,
+    pub size: GLint,
+}
+
+pub(crate) fn active_uniforms(program_id: GLuint) -> Vec<ActiveUniform> {
+    let mut count = 0;
+    let mut max_length = 0;
+    gl_call!(gl::GetProgramiv(program_id, gl::ACTIVE_UNIFORMS, &mut count));
+    gl_call!(gl::GetProgramiv(program_id, gl::ACTIVE_UNIFORM_MAX_LENGTH, &mut max_length));
+
+    let mut name: Vec<u8> = vec![0; max_length.max(1) as usize];
+    (0..count.max(0) as GLuint)
+        .map(|index| {
+            let mut length = 0;
+            let mut size = 0;
+            let mut gl_type = 0;
+            gl_call!(gl::GetActiveUniform(
+                program_id,
+                index,
+                name.len() as GLsizei,
+                &mut length,
+                &mut size,
+                &mut gl_type,
+                name.as_mut_ptr() as *mut GLchar
+            ));
+
+            ActiveUniform {
+                name: String::from_utf8_lossy(&name[..length.max(0) as usize]).into_owned(),
+                gl_type,
+                size,
+            }
+        })
+        .collect()
+}
+
+fn active_attributes(program_id: GLuint) -> Vec<ActiveUniform> {
+    let mut count = 0;
+    let mut max_length = 0;
+    gl_call!(gl::GetProgramiv(program_id, gl::ACTIVE_ATTRIBUTES, &mut count));
+    gl_call!(gl::GetProgramiv(program_id, gl::ACTIVE_ATTRIBUTE_MAX_LENGTH, &mut max_length));
+
+    let mut name: Vec<u8> = vec![0; max_length.max(1) as usize];
+    (0..count.max(0) as GLuint)
+        .map(|index| {
+            let mut length = 0;
+            let mut size = 0;
+            let mut gl_type = 0;
+            gl_call!(gl::GetActiveAttrib(
+                program_id,
+                index,
+                name.len() as GLsizei,
+                &mut length,
+                &mut size,
+                &mut gl_type,
+                name.as_mut_ptr() as *mut GLchar
+            ));
+
+            ActiveUniform {
+                name: String::from_utf8_lossy(&name[..length.max(0) as usize]).into_owned(),
+                gl_type,
+                size,
+            }
+        })
+        .collect()
+}
+
+fn uniform_location(program_id: GLuint, name: &str) -> GLint {
+    let location;
+    gl_call!(location = gl::GetUniformLocation(program_id, str_to_c_str_ptr!(name)));
+
+    location
+}
+
+pub(crate) fn is_sampler(gl_type: GLenum) -> bool {
+    matches!(
+        gl_type,
+        gl::SAMPLER_1D
+            | gl::SAMPLER_2D
+            | gl::SAMPLER_3D
+            | gl::SAMPLER_CUBE
+            | gl::SAMPLER_1D_SHADOW
+            | gl::SAMPLER_2D_SHADOW
+            | gl::SAMPLER_1D_ARRAY
+            | gl::SAMPLER_2D_ARRAY
+            | gl::SAMPLER_1D_ARRAY_SHADOW
+            | gl::SAMPLER_2D_ARRAY_SHADOW
+            | gl::SAMPLER_2D_MULTISAMPLE
+            | gl::SAMPLER_2D_MULTISAMPLE_ARRAY
+            | gl::SAMPLER_CUBE_SHADOW
+            | gl::SAMPLER_CUBE_MAP_ARRAY
+            | gl::SAMPLER_CUBE_MAP_ARRAY_SHADOW
+            | gl::SAMPLER_BUFFER
+            | gl::SAMPLER_2D_RECT
+            | gl::SAMPLER_2D_RECT_SHADOW
+            | gl::INT_SAMPLER_1D
+            | gl::INT_SAMPLER_2D
+            | gl::INT_SAMPLER_3D
+            | gl::INT_SAMPLER_CUBE
+            | gl::INT_SAMPLER_1D_ARRAY
+            | gl::INT_SAMPLER_2D_ARRAY
+            | gl::INT_SAMPLER_2D_MULTISAMPLE
+            | gl::INT_SAMPLER_2D_MULTISAMPLE_ARRAY
+            | gl::INT_SAMPLER_CUBE_MAP_ARRAY
+            | gl::INT_SAMPLER_BUFFER
+            | gl::INT_SAMPLER_2D_RECT
+            | gl::UNSIGNED_INT_SAMPLER_1D
+            | gl::UNSIGNED_INT_SAMPLER_2D
+            | gl::UNSIGNED_INT_SAMPLER_3D
+            | gl::UNSIGNED_INT_SAMPLER_CUBE
+            | gl::UNSIGNED_INT_SAMPLER_1D_ARRAY
+            | gl::UNSIGNED_INT_SAMPLER_2D_ARRAY
+            | gl::UNSIGNED_INT_SAMPLER_2D_MULTISAMPLE
+            | gl::UNSIGNED_INT_SAMPLER_2D_MULTISAMPLE_ARRAY
+            | gl::UNSIGNED_INT_SAMPLER_CUBE_MAP_ARRAY
+            | gl::UNSIGNED_INT_SAMPLER_BUFFER
+            | gl::UNSIGNED_INT_SAMPLER_2D_RECT
+    )
+}
+
+/// The GLSL name of a type returned by `glGetActiveUniform`, for messages.
+pub fn glsl_type_name(gl_type: GLenum) -> &'static str {
+    match gl_type {
+        gl::FLOAT => "float",
+        gl::FLOAT_VEC2 => "vec2",
+        gl::FLOAT_VEC3 => "vec3",
+        gl::FLOAT_VEC4 => "vec4",
+        gl::DOUBLE => "double",
+        gl::DOUBLE_VEC2 => "dvec2",
+        gl::DOUBLE_VEC3 => "dvec3",
+        gl::DOUBLE_VEC4 => "dvec4",
+        gl::INT => "int",
+        gl::INT_VEC2 => "ivec2",
+        gl::INT_VEC3 => "ivec3",
+        gl::INT_VEC4 => "ivec4",
+        gl::UNSIGNED_INT => "uint",
+        gl::UNSIGNED_INT_VEC2 => "uvec2",
+        gl::UNSIGNED_INT_VEC3 => "uvec3",
+        gl::UNSIGNED_INT_VEC4 => "uvec4",
+        gl::BOOL => "bool",
+        gl::BOOL_VEC2 => "bvec2",
+        gl::BOOL_VEC3 => "bvec3",
+        gl::BOOL_VEC4 => "bvec4",
+        gl::FLOAT_MAT2 => "mat2",
+        gl::FLOAT_MAT3 => "mat3",
+        gl::FLOAT_MAT4 => "mat4",
+        gl::FLOAT_MAT2x3 => "mat2x3",
+        gl::FLOAT_MAT2x4 => "mat2x4",
+        gl::FLOAT_MAT3x2 => "mat3x2",
+        gl::FLOAT_MAT3x4 => "mat3x4",
+        gl::FLOAT_MAT4x2 => "mat4x2",
+        gl::FLOAT_MAT4x3 => "mat4x3",
+        gl::DOUBLE_MAT2 => "dmat2",
+        gl::DOUBLE_MAT3 => "dmat3",
+        gl::DOUBLE_MAT4 => "dmat4",
+        gl::SAMPLER_1D => "sampler1D",
+        gl::SAMPLER_2D => "sampler2D",
+        gl::SAMPLER_3D => "sampler3D",
+        gl::SAMPLER_CUBE => "samplerCube",
+        gl::SAMPLER_2D_SHADOW => "sampler2DShadow",
+        gl::SAMPLER_2D_ARRAY => "sampler2DArray",
+        gl::INT_SAMPLER_2D => "isampler2D",
+        gl::UNSIGNED_INT_SAMPLER_2D => "usampler2D",
+        _ if is_sampler(gl_type) => "sampler",
+        _ => "unknown",
+    }
+}
@@ -1,39 +1,190 @@
 pub use crate::prelude::{ShaderObject, gl_call};
-use cgmath::{Vector3, Matrix4, Matrix};
+use super::shader_reflection::{glsl_type_name, is_sampler};
+use cgmath::{Matrix2, Matrix3, Matrix4, Point3, Quaternion, Vector2, Vector3, Vector4};
+use gl::types::*;
+use std::slice;
 
 pub trait ShaderUniform {
-    fn set_uniform(&self, shader: &mut impl ShaderObject, uniform_name: &str);
-}
+    /// Whether the value can be uploaded to a uniform of the GLSL `gl_type`
+    /// (as reported by `glGetActiveUniform`).
+    fn accepts(gl_type: GLenum) -> bool
+    where
+        Self: Sized;
+
+    /// Uploads the value to `location` of the currently bound program.
+    fn upload(&self, location: GLint);
 
-impl ShaderUniform for i32 {
-    fn set_uniform(&self, shader: &mut impl ShaderObject, uniform_name: &str) {
+    /// Looks the uniform up and uploads the value. In debug builds the GLSL
+    /// type is checked first and a mismatch is reported instead of uploaded.
+    fn set_uniform(&self, shader: &mut impl ShaderObject, uniform_name: &str)
+    where
+        Self: Sized,
+    {
+        let uniform_location = Self::checked_location(shader, uniform_name);
+        if uniform_location != -1 {
+            self.upload(uniform_location);
+        }
+    }
+
+    /// The location of `uniform_name`, or -1 if the uniform does not exist or
+    /// (in debug builds) has a GLSL type this type can not be uploaded to.
+    fn checked_location(shader: &mut impl ShaderObject, uniform_name: &str) -> GLint
+    where
+        Self: Sized,
+    {
         let uniform_location = shader.get_uniform_location(uniform_name);
-        gl_call!(gl::Uniform1i(uniform_location, *self));
+        if uniform_location == -1 {
+            return -1;
+        }
+
+        if cfg!(debug_assertions) {
+            if let Some(uniform) = shader.uniform_info(uniform_name) {
+                if !Self::accepts(uniform.gl_type) {
+                    log::error!(
+                        "Uniform {} is a {}, which can not be set from {}",
+                        uniform_name,
+                        glsl_type_name(uniform.gl_type),
+                        std::any::type_name::<Self>()
+                    );
+                    return -1;
+                }
+            }
+        }
+
+        uniform_location
     }
 }
 
-impl ShaderUniform for f32 {
-    fn set_uniform(&self, shader: &mut impl ShaderObject, uniform_name: &str) {
-        let uniform_location = shader.get_uniform_location(uniform_name);
-        gl_call!(gl::Uniform1f(uniform_location, *self));
+/// A value that maps to a single GLSL scalar, vector or matrix. Single values,
+/// slices and fixed size arrays of these implement `ShaderUniform`, arrays
+/// are uploaded with one `glUniform*v` call.
+pub trait UniformElement: Sized {
+    fn accepts(gl_type: GLenum) -> bool;
+    fn upload_slice(location: GLint, values: &[Self]);
+}
+
+impl<T: UniformElement> ShaderUniform for &[T] {
+    fn accepts(gl_type: GLenum) -> bool {
+        T::accepts(gl_type)
+    }
+
+    fn upload(&self, location: GLint) {
+        T::upload_slice(location, self);
     }
 }
 
-impl ShaderUniform for Vector3<f32> {
-    fn set_uniform(&self, shader: &mut impl ShaderObject, uniform_name: &str) {
-        let uniform_location = shader.get_uniform_location(uniform_name);
-        gl_call!(gl::Uniform3f(uniform_location, self[0], self[1], self[2]));
+impl<T: UniformElement, const N: usize> ShaderUniform for [T; N] {
+    fn accepts(gl_type: GLenum) -> bool {
+        T::accepts(gl_type)
+    }
+
+    fn upload(&self, location: GLint) {
+        T::upload_slice(location, self);
     }
 }
 
-impl ShaderUniform for Matrix4<f32> {
-    fn set_uniform(&self, shader: &mut impl ShaderObject, uniform_name: &str) {
-        let uniform_location = shader.get_uniform_location(uniform_name);
-        gl_call!(gl::UniformMatrix4fv(
-            uniform_location,
-            1,
-            gl::FALSE,
-            self.as_ptr()
-        ));
-    }
-}
\ No newline at end of file
+//  Every vector and matrix type of cgmath is `repr(C)`, so a slice of them can
+//  be passed as a pointer to their components.
+macro_rules! uniform_element {
+    ($element:ty, [$($gl_type:path),+], $function:ident, $component:ty) => {
+        impl UniformElement for $element {
+            fn accepts(gl_type: GLenum) -> bool {
+                matches!(gl_type, $($gl_type)|+)
+            }
+
+            fn upload_slice(location: GLint, values: &[Self]) {
+                gl_call!(gl::$function(
+                    location,
+                    values.len() as GLsizei,
+                    values.as_ptr() as *const $component
+                ));
+            }
+        }
+    };
+    ($element:ty, [$($gl_type:path),+], matrix $function:ident) => {
+        impl UniformElement for $element {
+            fn accepts(gl_type: GLenum) -> bool {
+                matches!(gl_type, $($gl_type)|+)
+            }
+
+            fn upload_slice(location: GLint, values: &[Self]) {
+                gl_call!(gl::$function(
+                    location,
+                    values.len() as GLsizei,
+                    gl::FALSE,
+                    values.as_ptr() as *const GLfloat
+                ));
+            }
+        }
+    };
+}
+
+macro_rules! shader_uniform {
+    ($($element:ty),+ $(,)?) => {
+        $(
+            impl ShaderUniform for $element {
+                fn accepts(gl_type: GLenum) -> bool {
+                    <$element as UniformElement>::accepts(gl_type)
+                }
+
+                fn upload(&self, location: GLint) {
+                    <$element as UniformElement>::upload_slice(location, slice::from_ref(self));
+                }
+            }
+        )+
+    };
+}
+
+impl UniformElement for i32 {
+    fn accepts(gl_type: GLenum) -> bool {
+        gl_type == gl::INT || gl_type == gl::BOOL || is_sampler(gl_type)
+    }
+
+    fn upload_slice(location: GLint, values: &[Self]) {
+        gl_call!(gl::Uniform1iv(location, values.len() as GLsizei, values.as_ptr()));
+    }
+}
+
+impl UniformElement for bool {
+    fn accepts(gl_type: GLenum) -> bool {
+        gl_type == gl::BOOL
+    }
+
+    fn upload_slice(location: GLint, values: &[Self]) {
+        let values: Vec<GLint> = values.iter().map(|value| *value as GLint).collect();
+        gl_call!(gl::Uniform1iv(location, values.len() as GLsizei, values.as_ptr()));
+    }
+}
+
+uniform_element!(f32, [gl::FLOAT, gl::BOOL], Uniform1fv, GLfloat);
+uniform_element!(u32, [gl::UNSIGNED_INT, gl::BOOL], Uniform1uiv, GLuint);
+uniform_element!(Vector2<f32>, [gl::FLOAT_VEC2, gl::BOOL_VEC2], Uniform2fv, GLfloat);
+uniform_element!(Vector3<f32>, [gl::FLOAT_VEC3, gl::BOOL_VEC3], Uniform3fv, GLfloat);
+uniform_element!(Vector4<f32>, [gl::FLOAT_VEC4, gl::BOOL_VEC4], Uniform4fv, GLfloat);
+uniform_element!(Vector2<i32>, [gl::INT_VEC2, gl::BOOL_VEC2], Uniform2iv, GLint);
+uniform_element!(Vector3<i32>, [gl::INT_VEC3, gl::BOOL_VEC3], Uniform3iv, GLint);
+uniform_element!(Vector4<i32>, [gl::INT_VEC4, gl::BOOL_VEC4], Uniform4iv, GLint);
+uniform_element!(Point3<f32>, [gl::FLOAT_VEC3], Uniform3fv, GLfloat);
+//  Stored as (x, y, z, w), which is what GLSL code expects of a vec4.
+uniform_element!(Quaternion<f32>, [gl::FLOAT_VEC4], Uniform4fv, GLfloat);
+uniform_element!(Matrix2<f32>, [gl::FLOAT_MAT2], matrix UniformMatrix2fv);
+uniform_element!(Matrix3<f32>, [gl::FLOAT_MAT3], matrix UniformMatrix3fv);
+uniform_element!(Matrix4<f32>, [gl::FLOAT_MAT4], matrix UniformMatrix4fv);
+
+shader_uniform!(
+    i32,
+    u32,
+    f32,
+    bool,
+    Vector2<f32>,
+    Vector3<f32>,
+    Vector4<f32>,
+    Vector2<i32>,
+    Vector3<i32>,
+    Vector4<i32>,
+    Point3<f32>,
+    Quaternion<f32>,
+    Matrix2<f32>,
+    Matrix3<f32>,
+    Matrix4<f32>,
+);
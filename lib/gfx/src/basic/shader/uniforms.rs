@@ -0,0 +1,38 @@
+use gl::types::GLint;
+use super::ShaderObject;
+use std::{any::TypeId, rc::Rc};
+
+/// A struct of shader parameters uploaded with one call, usually implemented
+/// with `#[derive(Uniforms)]`. Every field is a `ShaderUniform` and is
+/// uploaded to the uniform with the same name, unless it is renamed with
+/// `#[uniform(name = "...")]` or skipped with `#[uniform(skip)]`.
+pub trait Uniforms: 'static {
+    /// Looks up the location of every field, in declaration order. Fields
+    /// without a matching uniform get -1.
+    fn locate(shader: &mut impl ShaderObject) -> Vec<GLint>
+    where
+        Self: Sized;
+
+    /// Uploads the fields to `locations` of the currently bound program.
+    fn upload(&self, locations: &[GLint]);
+
+    /// Uploads every field. The locations are looked up by name only the
+    /// first time a program sees this type.
+    fn apply(&self, shader: &mut impl ShaderObject)
+    where
+        Self: Sized,
+    {
+        let key = TypeId::of::<Self>();
+        let locations = match shader.cached_uniform_locations(key) {
+            Some(locations) => locations,
+            None => {
+                let locations: Rc<[GLint]> = Self::locate(shader).into();
+                shader.cache_uniform_locations(key, locations.clone());
+                locations
+            }
+        };
+
+        shader.bind();
+        self.upload(&locations);
+    }
+}
@@ -0,0 +1,66 @@
+use crate::prelude::{Bindable, Labelable};
+use std::{ops::Deref, rc::Rc};
+
+/// A reference-counted handle to a gl object, for the cases where several
+/// owners really use the same texture or program. Cloning it only clones the
+/// handle; the object is deleted once the last clone is dropped.
+///
+/// It is `!Send` like the context the object lives in.
+pub struct Shared<T>(Rc<T>);
+
+impl<T> Shared<T> {
+    pub fn new(object: T) -> Shared<T> {
+        Shared(Rc::new(object))
+    }
+
+    /// Whether both handles point to the same object.
+    pub fn ptr_eq(this: &Shared<T>, other: &Shared<T>) -> bool {
+        Rc::ptr_eq(&this.0, &other.0)
+    }
+
+    /// The number of handles to the object.
+    pub fn count(this: &Shared<T>) -> usize {
+        Rc::strong_count(&this.0)
+    }
+
+    /// Returns the object if this is its only handle.
+    pub fn try_unwrap(this: Shared<T>) -> Result<T, Shared<T>> {
+        Rc::try_unwrap(this.0).map_err(Shared)
+    }
+}
+
+impl<T> Clone for Shared<T> {
+    fn clone(&self) -> Self {
+        Shared(Rc::clone(&self.0))
+    }
+}
+
+impl<T> Deref for Shared<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.0
+    }
+}
+
+impl<T> From<T> for Shared<T> {
+    fn from(object: T) -> Self {
+        Shared::new(object)
+    }
+}
+
+impl<T: Bindable> Bindable for Shared<T> {
+    fn bind(&self) {
+        self.0.bind();
+    }
+
+    fn unbind(&self) {
+        self.0.unbind();
+    }
+}
+
+impl<T: Labelable> Labelable for Shared<T> {
+    fn label(&self, label: &str) {
+        self.0.label(label);
+    }
+}
@@ -2,8 +2,10 @@ mod texture;
 mod texture_object;
 mod texture_builder;
 mod texture_data;
+mod texture_params;
 
 pub use texture_object::TextureObject;
 pub use texture::Texture;
 pub use texture_builder::TextureBuilder;
-pub use texture_data::TextureData;
\ No newline at end of file
+pub use texture_data::TextureData;
+pub use texture_params::{Filter, TextureFormat, Wrap};
\ No newline at end of file
@@ -1,15 +1,16 @@
 extern crate gl;
 use gl::types::*;
 
-use super::TextureData;
+use super::{TextureData, TextureFormat};
 use crate::{
-    prelude::{TextureObject, ShaderObject, Bindable, gl_call},
-    basic::ShaderUniform,
+    prelude::{TextureObject, Bindable, Labelable, gl_call},
+    basic::{object_label, track_object, ShaderUniform},
+    delete_object, GlContext,
 };
 use image::GenericImageView;
 use std::ffi::c_void;
 
-#[derive(Clone, TextureObject)]
+#[derive(TextureObject)]
 pub struct Texture {
     texture_id: GLuint,
     gl_type: GLenum,
@@ -17,9 +18,10 @@ pub struct Texture {
 }
 
 impl Texture {
-    pub fn new_empty(gl_type: GLenum) -> Texture {
+    pub fn new_empty(_context: &GlContext, gl_type: GLenum) -> Texture {
         let mut texture_id = 1;
         gl_call!(gl::GenTextures(1, &mut texture_id));
+        track_object(gl::TEXTURE, texture_id);
 
         Texture {
             texture_id,
@@ -28,7 +30,10 @@ impl Texture {
         }
     } 
 
+    /// Wraps an existing texture, which is deleted when the `Texture` is
+    /// dropped. Nothing else may delete it.
     pub fn from_raw(texture_id: GLuint, gl_type: GLenum, active_texture_number: GLenum) -> Texture {
+        track_object(gl::TEXTURE, texture_id);
         Texture {
             texture_id,
             gl_type,
@@ -36,7 +41,7 @@ impl Texture {
         }
     }
 
-    pub fn set_image(&self, texture_data: TextureData, internal_format: GLenum, format: GLenum) {
+    pub fn set_image(&self, texture_data: TextureData, internal_format: TextureFormat, format: TextureFormat) {
         self.bind();
 
         match texture_data {
@@ -48,11 +53,11 @@ impl Texture {
                 gl_call!(gl::TexImage2D(
                     self.gl_type,
                     0,
-                    internal_format as i32,
+                    internal_format.gl_type() as i32,
                     width as i32,
                     height as i32,
                     0,
-                    format,
+                    format.gl_type(),
                     gl::UNSIGNED_BYTE,
                     data.as_ptr() as *const c_void
                 ));
@@ -74,11 +79,11 @@ impl Texture {
                 gl_call!(gl::TexImage2D(
                     self.gl_type,
                     0,
-                    internal_format as i32,
+                    internal_format.gl_type() as i32,
                     image.width() as i32,
                     image.height() as i32,
                     0,
-                    format,
+                    format.gl_type(),
                     gl::UNSIGNED_BYTE,
                     &image.as_bytes()[0] as *const u8 as *const c_void
                 ));
@@ -101,17 +106,29 @@ impl Bindable for Texture {
     }
 }
 
+impl Labelable for Texture {
+    fn label(&self, label: &str) {
+        object_label(gl::TEXTURE, self.texture_id, label);
+    }
+}
+
 impl Drop for Texture {
     fn drop(&mut self) {
-        gl_call!(gl::DeleteTextures(1, &self.texture_id));
+        delete_object(gl::TEXTURE, self.texture_id);
     }
 }
 
 impl ShaderUniform for &Texture {
-    fn set_uniform(&self, shader: &mut impl ShaderObject, uniform_name: &str) {
-        let uniform_location = shader.get_uniform_location(uniform_name);
+    fn accepts(gl_type: GLenum) -> bool {
+        matches!(
+            gl_type,
+            gl::SAMPLER_2D | gl::SAMPLER_2D_SHADOW | gl::INT_SAMPLER_2D | gl::UNSIGNED_INT_SAMPLER_2D
+        )
+    }
+
+    fn upload(&self, location: GLint) {
         gl_call!(gl::Uniform1i(
-            uniform_location,
+            location,
             (self.active_texture_number - gl::TEXTURE0) as i32
         ));
     }
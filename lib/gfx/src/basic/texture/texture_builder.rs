@@ -4,23 +4,24 @@ use std::{
     path::Path,
 };
 use image::GenericImageView;
-use super::TextureData;
+use super::{Filter, TextureData, TextureFormat, Wrap};
 use crate::{
     basic::Texture,
     prelude::{gl_call},
+    GlContext,
 };
 
 pub struct TextureBuilder {
     gl_type: GLenum,
     texture_data: TextureData,
 
-    format: GLenum,
-    internal_format: GLenum,
+    format: TextureFormat,
+    internal_format: TextureFormat,
     active_texture_number: GLuint,
-    texture_wrap_s: GLenum,
-    texture_wrap_t: GLenum,
-    texture_min_filter: GLenum,
-    texture_mag_filter: GLenum,
+    texture_wrap_s: Wrap,
+    texture_wrap_t: Wrap,
+    texture_min_filter: Filter,
+    texture_mag_filter: Filter,
 
     use_mipmaps: bool,
 }
@@ -44,14 +45,14 @@ impl TextureBuilder {
 
                 TextureBuilder::default()
                     .texture_data(missing_texture)
-                    .internal_format(gl::RGBA)
-                    .format(gl::RGBA)
+                    .internal_format(TextureFormat::Rgba)
+                    .format(TextureFormat::Rgba)
             },
             Ok(image) => {
                 let format = match image.color() {
-                    image::ColorType::Rgb8 => gl::RGB,
-                    image::ColorType::Rgba8 => gl::RGBA,
-                    _ => gl::RGB,
+                    image::ColorType::Rgb8 => TextureFormat::Rgb,
+                    image::ColorType::Rgba8 => TextureFormat::Rgba,
+                    _ => TextureFormat::Rgb,
                 };
 
                 TextureBuilder::default()
@@ -79,12 +80,12 @@ impl TextureBuilder {
         self
     }
 
-    pub fn format(mut self, format: GLenum) -> TextureBuilder {
+    pub fn format(mut self, format: TextureFormat) -> TextureBuilder {
         self.format = format;
         self
     }
 
-    pub fn internal_format(mut self, internal_format: GLenum) -> TextureBuilder {
+    pub fn internal_format(mut self, internal_format: TextureFormat) -> TextureBuilder {
         self.internal_format = internal_format;
         self
     }
@@ -94,22 +95,22 @@ impl TextureBuilder {
         self
     }
 
-    pub fn texture_wrap_s(mut self, texture_wrap_s: GLenum) -> TextureBuilder {
+    pub fn texture_wrap_s(mut self, texture_wrap_s: Wrap) -> TextureBuilder {
         self.texture_wrap_s = texture_wrap_s;
         self
     }
 
-    pub fn texture_wrap_t(mut self, texture_wrap_t: GLenum) -> TextureBuilder {
+    pub fn texture_wrap_t(mut self, texture_wrap_t: Wrap) -> TextureBuilder {
         self.texture_wrap_t = texture_wrap_t;
         self
     }
 
-    pub fn texture_min_filter(mut self, texture_min_filter: GLenum) -> TextureBuilder {
+    pub fn texture_min_filter(mut self, texture_min_filter: Filter) -> TextureBuilder {
         self.texture_min_filter = texture_min_filter;
         self
     }
 
-    pub fn texture_mag_filter(mut self, texture_mag_filter: GLenum) -> TextureBuilder {
+    pub fn texture_mag_filter(mut self, texture_mag_filter: Filter) -> TextureBuilder {
         self.texture_mag_filter = texture_mag_filter;
         self
     }
@@ -124,7 +125,7 @@ impl TextureBuilder {
         self
     }
 
-    pub fn build(self) -> Texture {
+    pub fn build(self, _context: &GlContext) -> Texture {
         let mut texture_id = 1;
         gl_call!(gl::GenTextures(1, &mut texture_id));
 
@@ -134,22 +135,22 @@ impl TextureBuilder {
         gl_call!(gl::TexParameteri(
             self.gl_type,
             gl::TEXTURE_WRAP_S,
-            self.texture_wrap_s as i32
+            self.texture_wrap_s.gl_type() as i32
         ));
         gl_call!(gl::TexParameteri(
             self.gl_type,
             gl::TEXTURE_WRAP_T,
-            self.texture_wrap_t as i32
+            self.texture_wrap_t.gl_type() as i32
         ));
         gl_call!(gl::TexParameteri(
             self.gl_type,
             gl::TEXTURE_MIN_FILTER,
-            self.texture_min_filter as i32
+            self.texture_min_filter.gl_type() as i32
         ));
         gl_call!(gl::TexParameteri(
             self.gl_type,
             gl::TEXTURE_MAG_FILTER,
-            self.texture_mag_filter as i32
+            self.texture_mag_filter.gl_type() as i32
         ));
 
         match self.texture_data {
@@ -161,11 +162,11 @@ impl TextureBuilder {
                 gl_call!(gl::TexImage2D(
                     self.gl_type,
                     0,
-                    self.internal_format as i32,
+                    self.internal_format.gl_type() as i32,
                     width as i32,
                     height as i32,
                     0,
-                    self.format,
+                    self.format.gl_type(),
                     gl::UNSIGNED_BYTE,
                     data.as_ptr() as *const c_void
                 ));
@@ -187,11 +188,11 @@ impl TextureBuilder {
                 gl_call!(gl::TexImage2D(
                     self.gl_type,
                     0,
-                    self.internal_format as i32,
+                    self.internal_format.gl_type() as i32,
                     image.width() as i32,
                     image.height() as i32,
                     0,
-                    self.format,
+                    self.format.gl_type(),
                     gl::UNSIGNED_BYTE,
                     &image.as_bytes()[0] as *const u8 as *const c_void
                 ));
@@ -213,15 +214,72 @@ impl Default for TextureBuilder {
             gl_type: gl::TEXTURE_2D,
             texture_data: TextureData::None,
 
-            format: gl::RGB,
-            internal_format: gl::RGB,
+            format: TextureFormat::Rgb,
+            internal_format: TextureFormat::Rgb,
             active_texture_number: gl::TEXTURE0,
-            texture_wrap_s: gl::CLAMP_TO_BORDER,
-            texture_wrap_t: gl::CLAMP_TO_BORDER,
-            texture_min_filter: gl::NEAREST_MIPMAP_LINEAR,
-            texture_mag_filter: gl::NEAREST,
+            texture_wrap_s: Wrap::ClampToBorder,
+            texture_wrap_t: Wrap::ClampToBorder,
+            texture_min_filter: Filter::NearestMipmapLinear,
+            texture_mag_filter: Filter::Nearest,
 
             use_mipmaps: true,
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::backend::{record, GlCommand};
+
+    #[test]
+    fn build_sets_parameters_and_uploads_the_image() {
+        let commands = record(|| {
+            let context = unsafe { GlContext::new() };
+            TextureBuilder::from_raw_data(&[255; 16], 2, 2)
+                .format(TextureFormat::Rgba)
+                .internal_format(TextureFormat::Rgba8)
+                .active_texture_number(gl::TEXTURE1)
+                .texture_wrap_s(Wrap::Repeat)
+                .texture_wrap_t(Wrap::MirroredRepeat)
+                .texture_min_filter(Filter::Linear)
+                .texture_mag_filter(Filter::Nearest)
+                .use_mipmaps(false)
+                .build(&context);
+        });
+
+        let parameter = |pname, param: GLenum| GlCommand::TexParameteri {
+            target: gl::TEXTURE_2D,
+            pname,
+            param: param as GLint,
+        };
+        assert!(matches!(commands[0], GlCommand::GenTextures { n: 1, .. }));
+        assert_eq!(
+            commands[1..7],
+            [
+                GlCommand::ActiveTexture { texture: gl::TEXTURE1 },
+                GlCommand::BindTexture { target: gl::TEXTURE_2D, texture: 1 },
+                parameter(gl::TEXTURE_WRAP_S, gl::REPEAT),
+                parameter(gl::TEXTURE_WRAP_T, gl::MIRRORED_REPEAT),
+                parameter(gl::TEXTURE_MIN_FILTER, gl::LINEAR),
+                parameter(gl::TEXTURE_MAG_FILTER, gl::NEAREST),
+            ]
+        );
+        match commands[7] {
+            GlCommand::TexImage2D {
+                target,
+                internalformat,
+                width,
+                height,
+                format,
+                type_,
+                ..
+            } => assert_eq!(
+                (target, internalformat, width, height, format, type_),
+                (gl::TEXTURE_2D, gl::RGBA8 as GLint, 2, 2, gl::RGBA, gl::UNSIGNED_BYTE)
+            ),
+            ref command => panic!("expected TexImage2D, got {:?}", command),
+        }
+        assert!(!commands.iter().any(|command| matches!(command, GlCommand::GenerateMipmap { .. })));
+    }
+}
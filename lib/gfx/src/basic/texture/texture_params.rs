@@ -0,0 +1,52 @@
+use crate::basic::gl_enum;
+
+gl_enum! {
+    /// What a texture returns for coordinates outside of [0, 1].
+    pub enum Wrap {
+        Repeat => gl::REPEAT,
+        MirroredRepeat => gl::MIRRORED_REPEAT,
+        ClampToEdge => gl::CLAMP_TO_EDGE,
+        ClampToBorder => gl::CLAMP_TO_BORDER,
+        MirrorClampToEdge => gl::MIRROR_CLAMP_TO_EDGE,
+    }
+}
+
+gl_enum! {
+    /// How texels are sampled. The mipmap variants are only valid as minifying
+    /// filters.
+    pub enum Filter {
+        Nearest => gl::NEAREST,
+        Linear => gl::LINEAR,
+        NearestMipmapNearest => gl::NEAREST_MIPMAP_NEAREST,
+        LinearMipmapNearest => gl::LINEAR_MIPMAP_NEAREST,
+        NearestMipmapLinear => gl::NEAREST_MIPMAP_LINEAR,
+        LinearMipmapLinear => gl::LINEAR_MIPMAP_LINEAR,
+    }
+}
+
+gl_enum! {
+    /// The layout of texel data. The unsized formats describe the pixels
+    /// passed to OpenGL, the sized ones are only valid as internal formats.
+    pub enum TextureFormat {
+        Red => gl::RED,
+        Rg => gl::RG,
+        Rgb => gl::RGB,
+        Bgr => gl::BGR,
+        Rgba => gl::RGBA,
+        Bgra => gl::BGRA,
+        DepthComponent => gl::DEPTH_COMPONENT,
+        DepthStencil => gl::DEPTH_STENCIL,
+        R8 => gl::R8,
+        Rg8 => gl::RG8,
+        Rgb8 => gl::RGB8,
+        Rgba8 => gl::RGBA8,
+        Srgb8 => gl::SRGB8,
+        Srgb8Alpha8 => gl::SRGB8_ALPHA8,
+        R16F => gl::R16F,
+        Rgba16F => gl::RGBA16F,
+        R32F => gl::R32F,
+        Rgba32F => gl::RGBA32F,
+        DepthComponent24 => gl::DEPTH_COMPONENT24,
+        Depth24Stencil8 => gl::DEPTH24_STENCIL8,
+    }
+}
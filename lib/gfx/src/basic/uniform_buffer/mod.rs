@@ -0,0 +1,5 @@
+mod std140;
+mod uniform_buffer;
+
+pub use std140::Std140;
+pub use uniform_buffer::UniformBuffer;
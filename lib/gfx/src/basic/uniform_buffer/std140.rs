@@ -0,0 +1,218 @@
+use cgmath::{Matrix2, Matrix3, Matrix4, Point3, Quaternion, Vector2, Vector3, Vector4};
+use std::mem;
+
+/// A value with a known std140 layout, so it can be stored in a uniform
+/// block. Implemented for the GLSL scalar, vector and matrix types, arrays of
+/// them and structs with `#[derive(Std140)]`.
+pub trait Std140 {
+    /// The base alignment of the type.
+    const ALIGN: usize;
+    /// The size of the type, including the padding at its end.
+    const SIZE: usize;
+
+    /// Writes the value to `buffer`, which is exactly `SIZE` bytes long.
+    /// Padding bytes are left untouched.
+    fn write_std140(&self, buffer: &mut [u8]);
+}
+
+//  Rounds `offset` up to the next multiple of `align`.
+const fn align_to(offset: usize, align: usize) -> usize {
+    offset.div_ceil(align) * align
+}
+
+fn write_components<T: Copy, const N: usize>(
+    buffer: &mut [u8],
+    components: [T; N],
+    to_bytes: fn(T) -> [u8; 4],
+) {
+    for (component, bytes) in components.iter().zip(buffer.chunks_exact_mut(4)) {
+        bytes.copy_from_slice(&to_bytes(*component));
+    }
+}
+
+impl Std140 for f32 {
+    const ALIGN: usize = 4;
+    const SIZE: usize = 4;
+
+    fn write_std140(&self, buffer: &mut [u8]) {
+        buffer.copy_from_slice(&self.to_ne_bytes());
+    }
+}
+
+impl Std140 for i32 {
+    const ALIGN: usize = 4;
+    const SIZE: usize = 4;
+
+    fn write_std140(&self, buffer: &mut [u8]) {
+        buffer.copy_from_slice(&self.to_ne_bytes());
+    }
+}
+
+impl Std140 for u32 {
+    const ALIGN: usize = 4;
+    const SIZE: usize = 4;
+
+    fn write_std140(&self, buffer: &mut [u8]) {
+        buffer.copy_from_slice(&self.to_ne_bytes());
+    }
+}
+
+//  GLSL bools are 4 bytes wide in uniform blocks.
+impl Std140 for bool {
+    const ALIGN: usize = 4;
+    const SIZE: usize = 4;
+
+    fn write_std140(&self, buffer: &mut [u8]) {
+        buffer.copy_from_slice(&(*self as u32).to_ne_bytes());
+    }
+}
+
+macro_rules! std140_vector {
+    ($vector:ident, $component:ty, $align:expr, [$($field:ident),+]) => {
+        impl Std140 for $vector<$component> {
+            const ALIGN: usize = $align;
+            const SIZE: usize = 4 * [$(stringify!($field)),+].len();
+
+            fn write_std140(&self, buffer: &mut [u8]) {
+                write_components(buffer, [$(self.$field),+], <$component>::to_ne_bytes);
+            }
+        }
+    };
+}
+
+std140_vector!(Vector2, f32, 8, [x, y]);
+std140_vector!(Vector3, f32, 16, [x, y, z]);
+std140_vector!(Vector4, f32, 16, [x, y, z, w]);
+std140_vector!(Vector2, i32, 8, [x, y]);
+std140_vector!(Vector3, i32, 16, [x, y, z]);
+std140_vector!(Vector4, i32, 16, [x, y, z, w]);
+std140_vector!(Vector2, u32, 8, [x, y]);
+std140_vector!(Vector3, u32, 16, [x, y, z]);
+std140_vector!(Vector4, u32, 16, [x, y, z, w]);
+std140_vector!(Point3, f32, 16, [x, y, z]);
+
+//  A quaternion is a vec4 of (x, y, z, w).
+impl Std140 for Quaternion<f32> {
+    const ALIGN: usize = 16;
+    const SIZE: usize = 16;
+
+    fn write_std140(&self, buffer: &mut [u8]) {
+        let components = [self.v.x, self.v.y, self.v.z, self.s];
+        write_components(buffer, components, f32::to_ne_bytes);
+    }
+}
+
+//  Matrices are stored like arrays of their column vectors, so every column
+//  starts at a multiple of 16 bytes.
+macro_rules! std140_matrix {
+    ($matrix:ident, [$($column:ident),+]) => {
+        impl Std140 for $matrix<f32> {
+            const ALIGN: usize = 16;
+            const SIZE: usize = 16 * [$(stringify!($column)),+].len();
+
+            fn write_std140(&self, buffer: &mut [u8]) {
+                let columns = buffer.chunks_exact_mut(16);
+                for (column, bytes) in [$(&self.$column),+].iter().zip(columns) {
+                    column.write_std140(&mut bytes[..mem::size_of_val(*column)]);
+                }
+            }
+        }
+    };
+}
+
+std140_matrix!(Matrix2, [x, y]);
+std140_matrix!(Matrix3, [x, y, z]);
+std140_matrix!(Matrix4, [x, y, z, w]);
+
+//  Array elements are aligned to 16 bytes, whatever their type.
+impl<T: Std140, const N: usize> Std140 for [T; N] {
+    const ALIGN: usize = align_to(T::ALIGN, 16);
+    const SIZE: usize = align_to(T::SIZE, Self::ALIGN) * N;
+
+    fn write_std140(&self, buffer: &mut [u8]) {
+        let stride = align_to(T::SIZE, Self::ALIGN);
+        for (element, bytes) in self.iter().zip(buffer.chunks_exact_mut(stride)) {
+            element.write_std140(&mut bytes[..T::SIZE]);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::derives::Std140;
+    use std::convert::TryInto;
+
+    #[derive(Std140)]
+    struct Light {
+        position: Vector3<f32>,
+        intensity: f32,
+    }
+
+    #[derive(Std140)]
+    struct Material {
+        albedo: Vector3<f32>,
+        uv_scale: Vector2<f32>,
+    }
+
+    //  Padding is filled with 0xaa, which `write_std140` has to leave alone.
+    fn std140_bytes<T: Std140>(value: &T) -> Vec<u8> {
+        let mut buffer = vec![0xaa; T::SIZE];
+        value.write_std140(&mut buffer);
+        buffer
+    }
+
+    fn f32_at(buffer: &[u8], offset: usize) -> f32 {
+        f32::from_ne_bytes(buffer[offset..offset + 4].try_into().unwrap())
+    }
+
+    #[test]
+    fn a_float_after_a_vec3_shares_its_16_bytes() {
+        assert_eq!((Light::ALIGN, Light::SIZE), (16, 16));
+
+        let buffer = std140_bytes(&Light {
+            position: Vector3::new(1.0, 2.0, 3.0),
+            intensity: 4.0,
+        });
+        let floats: Vec<f32> = (0..4).map(|index| f32_at(&buffer, index * 4)).collect();
+        assert_eq!(floats, [1.0, 2.0, 3.0, 4.0]);
+    }
+
+    #[test]
+    fn array_elements_have_a_16_byte_stride() {
+        assert_eq!((<[f32; 3]>::ALIGN, <[f32; 3]>::SIZE), (16, 48));
+
+        let buffer = std140_bytes(&[1.0f32, 2.0, 3.0]);
+        assert_eq!([f32_at(&buffer, 0), f32_at(&buffer, 16), f32_at(&buffer, 32)], [1.0, 2.0, 3.0]);
+        assert!(buffer[4..16].iter().all(|byte| *byte == 0xaa));
+    }
+
+    #[test]
+    fn mat3_columns_are_padded_to_16_bytes() {
+        assert_eq!((Matrix3::<f32>::ALIGN, Matrix3::<f32>::SIZE), (16, 48));
+
+        let matrix = Matrix3::new(1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0, 9.0);
+        let buffer = std140_bytes(&matrix);
+        for column in 0..3 {
+            let floats: Vec<f32> = (0..3).map(|row| f32_at(&buffer, column * 16 + row * 4)).collect();
+            let first = column as f32 * 3.0 + 1.0;
+            assert_eq!(floats, [first, first + 1.0, first + 2.0]);
+            assert!(buffer[column * 16 + 12..column * 16 + 16].iter().all(|byte| *byte == 0xaa));
+        }
+    }
+
+    #[test]
+    fn derived_structs_are_rounded_up_to_16_bytes() {
+        //  albedo at 0, uv_scale at the next multiple of 8 after 12, so the
+        //  fields end at 24.
+        assert_eq!((Material::ALIGN, Material::SIZE), (16, 32));
+
+        let buffer = std140_bytes(&Material {
+            albedo: Vector3::new(1.0, 2.0, 3.0),
+            uv_scale: Vector2::new(4.0, 5.0),
+        });
+        assert_eq!(f32_at(&buffer, 8), 3.0);
+        assert_eq!([f32_at(&buffer, 16), f32_at(&buffer, 20)], [4.0, 5.0]);
+        assert!(buffer[12..16].iter().chain(&buffer[24..]).all(|byte| *byte == 0xaa));
+    }
+}
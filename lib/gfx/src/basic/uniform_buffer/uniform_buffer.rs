@@ -0,0 +1,90 @@
+use gl::types::*;
+use super::Std140;
+use crate::{
+    basic::{Buffer, BufferUsage, UniformTarget},
+    prelude::{Bindable, Labelable},
+    GlContext,
+};
+use std::marker::PhantomData;
+
+/// A `GL_UNIFORM_BUFFER` holding one `T` in std140 layout. Bind it to a
+/// binding point with `bind_base`, and the uniform blocks of every program
+/// bound to the same point (see `Shader::bind_uniform_block`) read from it.
+pub struct UniformBuffer<T: Std140> {
+    buffer: Buffer<u8, UniformTarget>,
+    //  Staging memory, so padding stays zeroed between updates.
+    data: Vec<u8>,
+    _marker: PhantomData<T>,
+}
+
+impl<T: Std140> UniformBuffer<T> {
+    pub fn new(context: &GlContext, value: &T) -> UniformBuffer<T> {
+        let mut data = vec![0; T::SIZE];
+        value.write_std140(&mut data);
+
+        UniformBuffer {
+            buffer: Buffer::with_data(context, &data, BufferUsage::DynamicDraw),
+            data,
+            _marker: PhantomData,
+        }
+    }
+
+    pub fn update(&mut self, value: &T) {
+        value.write_std140(&mut self.data);
+        self.buffer.update_range(0, &self.data);
+    }
+
+    /// Binds the buffer to the uniform block binding point `binding`.
+    pub fn bind_base(&self, binding: GLuint) {
+        self.buffer.bind_base(binding);
+    }
+}
+
+impl<T: Std140> Bindable for UniformBuffer<T> {
+    #[inline]
+    fn bind(&self) {
+        self.buffer.bind();
+    }
+
+    #[inline]
+    fn unbind(&self) {
+        self.buffer.unbind();
+    }
+}
+
+impl<T: Std140> Labelable for UniformBuffer<T> {
+    fn label(&self, label: &str) {
+        self.buffer.label(label);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::backend::{record, GlCommand};
+
+    #[test]
+    fn update_rewrites_the_whole_value_in_place() {
+        let commands = record(|| {
+            let context = unsafe { GlContext::new() };
+            let mut buffer = UniformBuffer::new(&context, &[1.0f32, 2.0]);
+            buffer.update(&[3.0, 4.0]);
+            buffer.bind_base(2);
+        });
+
+        assert!(commands.iter().any(|command| matches!(
+            command,
+            GlCommand::BufferData { target: gl::UNIFORM_BUFFER, size: 32, .. }
+        )));
+        assert!(commands.iter().any(|command| matches!(
+            command,
+            GlCommand::NamedBufferSubData { buffer: 1, offset: 0, size: 32, .. }
+        )));
+        assert!(commands.contains(&GlCommand::BindBufferBase {
+            target: gl::UNIFORM_BUFFER,
+            index: 2,
+            buffer: 1,
+        }));
+        assert!(commands.iter().any(|command| matches!(command, GlCommand::DeleteBuffers { n: 1, .. })));
+    }
+}
@@ -0,0 +1,20 @@
+use crate::basic::gl_enum;
+
+gl_enum! {
+    /// The component type of a vertex attribute as it is stored in the buffer.
+    pub enum AttribType {
+        Byte => gl::BYTE,
+        UnsignedByte => gl::UNSIGNED_BYTE,
+        Short => gl::SHORT,
+        UnsignedShort => gl::UNSIGNED_SHORT,
+        Int => gl::INT,
+        UnsignedInt => gl::UNSIGNED_INT,
+        HalfFloat => gl::HALF_FLOAT,
+        Float => gl::FLOAT,
+        Double => gl::DOUBLE,
+        Fixed => gl::FIXED,
+        Int2101010Rev => gl::INT_2_10_10_10_REV,
+        UnsignedInt2101010Rev => gl::UNSIGNED_INT_2_10_10_10_REV,
+        UnsignedInt10F11F11FRev => gl::UNSIGNED_INT_10F_11F_11F_REV,
+    }
+}
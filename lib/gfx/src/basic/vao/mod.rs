@@ -1,7 +1,13 @@
+mod attrib_type;
 mod vao_object;
 mod vao;
 mod vao_layout;
+mod vao_layout_error;
+mod vertex;
 
-pub use vao::Vao;
-pub use vao_layout::VaoLayout;
-pub use vao_object::VaoObject;
\ No newline at end of file
+pub use attrib_type::AttribType;
+pub use vao::{Vao, VertexStream};
+pub use vao_layout::{AttributeKind, VaoLayout};
+pub use vao_layout_error::{LayoutError, LayoutMismatch};
+pub use vao_object::VaoObject;
+pub use vertex::{Vertex, VertexAttribute, VertexComponent};
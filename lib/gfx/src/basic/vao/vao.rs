@@ -1,16 +1,88 @@
 use gl::types::*;
-use crate::prelude::{gl_call, VaoObject, Bindable};
+use super::VaoLayout;
+use crate::{
+    basic::{object_label, track_object, Ebo, Pod, Vbo},
+    delete_object,
+    prelude::{gl_call, VaoObject, Bindable, Labelable},
+    GlContext,
+};
 
-#[derive(Clone, VaoObject)]
+#[derive(VaoObject)]
 pub struct Vao(GLuint);
 
+/// A `Vbo` of any element type together with the layout of its vertices, so
+/// buffers of different vertex types can be passed together (see
+/// `Pipeline::apply_streams`).
+pub struct VertexStream<'a> {
+    buffer_id: GLuint,
+    layout: &'a VaoLayout,
+}
+
+impl<'a> VertexStream<'a> {
+    pub fn new<T: Pod>(vbo: &'a Vbo<T>, layout: &'a VaoLayout) -> VertexStream<'a> {
+        VertexStream {
+            buffer_id: vbo.id(),
+            layout,
+        }
+    }
+
+    pub fn layout(&self) -> &VaoLayout {
+        self.layout
+    }
+}
+
 impl Vao {
-    pub fn new() -> Vao {
+    pub fn new(_context: &GlContext) -> Vao {
         let mut vao_id = 0;
-        gl_call!(gl::GenVertexArrays(1, &mut vao_id));
+        gl_call!(gl::CreateVertexArrays(1, &mut vao_id));
+        track_object(gl::VERTEX_ARRAY, vao_id);
 
         Vao(vao_id)
     }
+
+    pub(crate) fn id(&self) -> GLuint {
+        self.0
+    }
+
+    /// Feeds the attributes of `layout` from `vbo` through the buffer binding
+    /// slot `binding`. Every slot has its own buffer, stride and divisor, so
+    /// e.g. positions and UVs can live in separate buffers.
+    pub fn set_vertex_buffer<T: Pod>(&self, binding: GLuint, vbo: &Vbo<T>, layout: &VaoLayout) {
+        self.set_stream(binding, &VertexStream::new(vbo, layout));
+    }
+
+    /// Replaces the buffer of a slot, keeping the attribute formats set by
+    /// `set_vertex_buffer`. `offset` is where the first vertex starts.
+    pub fn bind_vertex_buffer<T: Pod>(
+        &self,
+        binding: GLuint,
+        vbo: &Vbo<T>,
+        offset: usize,
+        stride: usize,
+    ) {
+        self.bind_vertex_buffer_id(binding, vbo.id(), offset, stride);
+    }
+
+    pub(crate) fn set_stream(&self, binding: GLuint, stream: &VertexStream) {
+        stream.layout.apply_format(self, binding);
+        self.bind_vertex_buffer_id(binding, stream.buffer_id, 0, stream.layout.stride());
+    }
+
+    fn bind_vertex_buffer_id(&self, binding: GLuint, buffer_id: GLuint, offset: usize, stride: usize) {
+        gl_call!(gl::VertexArrayVertexBuffer(
+            self.0,
+            binding,
+            buffer_id,
+            offset as GLintptr,
+            stride as GLsizei
+        ));
+    }
+
+    /// Stores `ebo` as the index buffer of the vao, so binding the vao is
+    /// enough for indexed draws.
+    pub fn set_element_buffer<T: Pod>(&self, ebo: &Ebo<T>) {
+        gl_call!(gl::VertexArrayElementBuffer(self.0, ebo.id()));
+    }
 }
 
 impl Bindable for Vao {
@@ -25,8 +97,14 @@ impl Bindable for Vao {
     }
 }
 
+impl Labelable for Vao {
+    fn label(&self, label: &str) {
+        object_label(gl::VERTEX_ARRAY, self.0, label);
+    }
+}
+
 impl Drop for Vao {
     fn drop(&mut self) {
-        gl_call!(gl::DeleteVertexArrays(1, &self.0));
+        delete_object(gl::VERTEX_ARRAY, self.0);
     }
 }
\ No newline at end of file
@@ -1,17 +1,38 @@
 use gl::types::*;
 use utils::size_of_gl_type;
-use crate::prelude::{gl_call, VaoObject};
+use super::{AttribType, LayoutError, LayoutMismatch};
+use crate::{
+    basic::{ShaderReflection, Vao},
+    prelude::{gl_call, VaoObject},
+};
 use std::ffi::c_void;
 
+/// How the shader receives the components of an element.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AttributeKind {
+    /// Converted to float (`glVertexAttribPointer`), integer components are
+    /// mapped to [0, 1] or [-1, 1] when `normalized`.
+    Float { normalized: bool },
+    /// Integer components kept as they are, for `int`/`uint` attributes
+    /// (`glVertexAttribIPointer`).
+    Integer,
+    /// `GL_DOUBLE` components for `double`/`dvec` attributes
+    /// (`glVertexAttribLPointer`).
+    Double,
+}
+
 struct VaoLayoutElement {
+    location: GLuint,
+    offset: usize,
     count: u8,
     gl_type: GLenum,
-    normalized: GLboolean,
+    kind: AttributeKind,
 }
 
 pub struct VaoLayout {
     layout: Vec<VaoLayoutElement>,
     stride: usize,
+    divisor: GLuint,
 }
 
 #[allow(dead_code)]
@@ -20,16 +41,96 @@ impl VaoLayout {
         VaoLayout {
             layout: Vec::<VaoLayoutElement>::new(),
             stride: 0,
+            divisor: 0,
+        }
+    }
+
+    /// Pushes an element at the location after the previous element's one
+    /// (0 for the first element).
+    pub fn push_element(&mut self, count: u8, attrib_type: AttribType, normalized: bool) {
+        self.push_element_at(self.next_location(), count, attrib_type, normalized);
+    }
+
+    /// Pushes an element for an `int`, `uint` or integer vector attribute.
+    pub fn push_integer_element(&mut self, count: u8, attrib_type: AttribType) {
+        let (location, offset) = (self.next_location(), self.stride);
+        self.push_element_with_offset(location, offset, count, attrib_type, AttributeKind::Integer);
+    }
+
+    /// Pushes an element for a `double` or `dvec` attribute.
+    pub fn push_double_element(&mut self, count: u8) {
+        let (location, offset) = (self.next_location(), self.stride);
+        self.push_element_with_offset(location, offset, count, AttribType::Double, AttributeKind::Double);
+    }
+
+    /// An empty layout for vertices of `stride` bytes, for elements pushed at
+    /// explicit offsets with `push_element_with_offset`.
+    pub fn with_stride(stride: usize) -> VaoLayout {
+        VaoLayout {
+            layout: Vec::<VaoLayoutElement>::new(),
+            stride,
+            divisor: 0,
         }
     }
 
-    pub fn push_element(&mut self, count: u8, gl_type: GLenum, normalized: bool) {
+    /// Pushes an element feeding the attribute declared with
+    /// `layout (location = N)` in the shader.
+    pub fn push_element_at(&mut self, location: GLuint, count: u8, attrib_type: AttribType, normalized: bool) {
+        let offset = self.stride;
+        let kind = AttributeKind::Float { normalized };
+        self.push_element_with_offset(location, offset, count, attrib_type, kind);
+    }
+
+    /// Pushes an element starting `offset` bytes into the vertex. The stride
+    /// grows if the element ends after it. Packed types are 4 bytes wide and
+    /// take a `count` of 4 (`AttribType::Int2101010Rev`,
+    /// `AttribType::UnsignedInt2101010Rev`) or 3
+    /// (`AttribType::UnsignedInt10F11F11FRev`). Elements whose `kind` cannot
+    /// be fed with `attrib_type` are reported by `validate`.
+    pub fn push_element_with_offset(
+        &mut self,
+        location: GLuint,
+        offset: usize,
+        count: u8,
+        attrib_type: AttribType,
+        kind: AttributeKind,
+    ) {
+        let gl_type = attrib_type.gl_type();
         self.layout.push(VaoLayoutElement {
+            location,
+            offset,
             count,
             gl_type,
-            normalized: normalized as GLboolean,
+            kind,
         });
-        self.stride += count as usize * size_of_gl_type(gl_type).unwrap();
+        //  Elements of unknown size are reported by `validate`.
+        let end = offset + element_size(count, gl_type).unwrap_or(0);
+        self.stride = self.stride.max(end);
+    }
+
+    pub fn stride(&self) -> usize {
+        self.stride
+    }
+
+    /// Makes every element of the layout per-instance: the attributes advance
+    /// once every `divisor` instances instead of once per vertex. 0 (the
+    /// default) makes them per-vertex again.
+    ///
+    /// The divisor is per layout rather than per element, as OpenGL sets it
+    /// per buffer binding (`glVertexArrayBindingDivisor`) and a layout
+    /// describes the contents of one buffer. Attributes advancing at other
+    /// rates go into a buffer with a layout of their own (see
+    /// `Pipeline::apply_streams`).
+    pub fn set_divisor(&mut self, divisor: GLuint) {
+        self.divisor = divisor;
+    }
+
+    pub fn divisor(&self) -> GLuint {
+        self.divisor
+    }
+
+    fn next_location(&self) -> GLuint {
+        self.layout.last().map_or(0, |element| element.location + 1)
     }
 
     pub fn apply_layout<T>(&self, vao: &T)
@@ -44,24 +145,535 @@ impl VaoLayout {
     pub fn apply_layout_raw(&self) {
         apply_layout(self);
     }
+
+    //  Sets the formats of the elements on `vao` and sources them from the
+    //  buffer binding slot `binding`, see `Vao::set_vertex_buffer`.
+    pub(crate) fn apply_format(&self, vao: &Vao, binding: GLuint) {
+        let vao_id = vao.id();
+        for elem in self.layout.iter() {
+            let offset = elem.offset as GLuint;
+            match elem.kind {
+                AttributeKind::Float { normalized } => gl_call!(gl::VertexArrayAttribFormat(
+                    vao_id,
+                    elem.location,
+                    elem.count as GLint,
+                    elem.gl_type,
+                    normalized as GLboolean,
+                    offset
+                )),
+                AttributeKind::Integer => gl_call!(gl::VertexArrayAttribIFormat(
+                    vao_id,
+                    elem.location,
+                    elem.count as GLint,
+                    elem.gl_type,
+                    offset
+                )),
+                AttributeKind::Double => gl_call!(gl::VertexArrayAttribLFormat(
+                    vao_id,
+                    elem.location,
+                    elem.count as GLint,
+                    elem.gl_type,
+                    offset
+                )),
+            }
+            gl_call!(gl::VertexArrayAttribBinding(vao_id, elem.location, binding));
+            gl_call!(gl::EnableVertexArrayAttrib(vao_id, elem.location));
+        }
+        gl_call!(gl::VertexArrayBindingDivisor(vao_id, binding, self.divisor));
+    }
+
+    /// Compares the layout with the active attributes of a program. Every
+    /// element has to be valid on its own, and every attribute location has
+    /// to be fed by exactly one element with a matching kind and component
+    /// count. Elements feeding no attribute are only
+    /// logged as warnings.
+    pub fn validate(&self, reflection: &ShaderReflection) -> Result<(), LayoutError> {
+        VaoLayout::validate_combined(&[self], reflection)
+    }
+
+    /// Like `validate`, for attributes fed from several buffers (e.g. one per
+    /// vertex and one per instance), each with its own layout.
+    pub fn validate_combined(
+        layouts: &[&VaoLayout],
+        reflection: &ShaderReflection,
+    ) -> Result<(), LayoutError> {
+        let elements: Vec<&VaoLayoutElement> =
+            layouts.iter().flat_map(|layout| layout.layout.iter()).collect();
+        let mut mismatches = Vec::new();
+
+        //  Matrices take one location per column.
+        let mut locations = Vec::new();
+        for attribute in reflection.attributes() {
+            //  Built-ins like gl_VertexID have no location.
+            if attribute.location < 0 {
+                continue;
+            }
+
+            let (columns, components) = attribute_shape(attribute.gl_type);
+            let slots = columns * attribute.size.max(1) as u32;
+            for slot in 0..slots {
+                locations.push((attribute, attribute.location as GLuint + slot, components));
+            }
+        }
+
+        for (attribute, location, components) in locations.iter() {
+            let element = match elements.iter().find(|element| element.location == *location) {
+                Some(element) => element,
+                None => {
+                    mismatches.push(LayoutMismatch::Missing {
+                        name: attribute.name.clone(),
+                        location: *location,
+                        gl_type: attribute.gl_type,
+                    });
+                    continue;
+                }
+            };
+
+            let kind_matches = match element.kind {
+                AttributeKind::Float { .. } => is_float_attribute(attribute.gl_type),
+                AttributeKind::Integer => is_integer_attribute(attribute.gl_type),
+                AttributeKind::Double => is_double_attribute(attribute.gl_type),
+            };
+            if !kind_matches || element.count as u32 != *components {
+                mismatches.push(LayoutMismatch::TypeMismatch {
+                    name: attribute.name.clone(),
+                    location: *location,
+                    attribute_type: attribute.gl_type,
+                    count: element.count,
+                    gl_type: element.gl_type,
+                    kind: element.kind,
+                });
+            }
+        }
+
+        for element in elements.iter() {
+            if !is_valid_element(element) {
+                mismatches.push(LayoutMismatch::InvalidElement {
+                    location: element.location,
+                    count: element.count,
+                    gl_type: element.gl_type,
+                    kind: element.kind,
+                });
+            }
+        }
+
+        for (index, element) in elements.iter().enumerate() {
+            if elements[..index].iter().any(|other| other.location == element.location) {
+                mismatches.push(LayoutMismatch::DuplicateLocation {
+                    location: element.location,
+                });
+            } else if !locations.iter().any(|(_, location, _)| *location == element.location) {
+                //  Drivers drop attributes the shader does not use, so this
+                //  often just means a shader variant ignores part of the
+                //  vertex. Feeding them anyway is harmless.
+                log::warn!(
+                    "{}",
+                    LayoutMismatch::Extra {
+                        location: element.location,
+                        count: element.count,
+                        gl_type: element.gl_type,
+                    }
+                );
+            }
+        }
+
+        if mismatches.is_empty() {
+            Ok(())
+        } else {
+            Err(LayoutError { mismatches })
+        }
+    }
+}
+
+//  (locations, components per location) of an attribute type.
+fn attribute_shape(gl_type: GLenum) -> (u32, u32) {
+    match gl_type {
+        gl::FLOAT | gl::INT | gl::UNSIGNED_INT | gl::DOUBLE => (1, 1),
+        gl::FLOAT_VEC2 | gl::INT_VEC2 | gl::UNSIGNED_INT_VEC2 | gl::DOUBLE_VEC2 => (1, 2),
+        gl::FLOAT_VEC3 | gl::INT_VEC3 | gl::UNSIGNED_INT_VEC3 | gl::DOUBLE_VEC3 => (1, 3),
+        gl::FLOAT_VEC4 | gl::INT_VEC4 | gl::UNSIGNED_INT_VEC4 | gl::DOUBLE_VEC4 => (1, 4),
+        gl::FLOAT_MAT2 | gl::DOUBLE_MAT2 => (2, 2),
+        gl::FLOAT_MAT3 | gl::DOUBLE_MAT3 => (3, 3),
+        gl::FLOAT_MAT4 | gl::DOUBLE_MAT4 => (4, 4),
+        gl::FLOAT_MAT2x3 | gl::DOUBLE_MAT2x3 => (2, 3),
+        gl::FLOAT_MAT2x4 | gl::DOUBLE_MAT2x4 => (2, 4),
+        gl::FLOAT_MAT3x2 | gl::DOUBLE_MAT3x2 => (3, 2),
+        gl::FLOAT_MAT3x4 | gl::DOUBLE_MAT3x4 => (3, 4),
+        gl::FLOAT_MAT4x2 | gl::DOUBLE_MAT4x2 => (4, 2),
+        gl::FLOAT_MAT4x3 | gl::DOUBLE_MAT4x3 => (4, 3),
+        _ => (1, 4),
+    }
+}
+
+//  Every kind of element can only feed its own kind of attribute, anything
+//  else reads garbage.
+fn is_float_attribute(gl_type: GLenum) -> bool {
+    matches!(
+        gl_type,
+        gl::FLOAT
+            | gl::FLOAT_VEC2
+            | gl::FLOAT_VEC3
+            | gl::FLOAT_VEC4
+            | gl::FLOAT_MAT2
+            | gl::FLOAT_MAT3
+            | gl::FLOAT_MAT4
+            | gl::FLOAT_MAT2x3
+            | gl::FLOAT_MAT2x4
+            | gl::FLOAT_MAT3x2
+            | gl::FLOAT_MAT3x4
+            | gl::FLOAT_MAT4x2
+            | gl::FLOAT_MAT4x3
+    )
+}
+
+fn is_integer_attribute(gl_type: GLenum) -> bool {
+    matches!(
+        gl_type,
+        gl::INT
+            | gl::INT_VEC2
+            | gl::INT_VEC3
+            | gl::INT_VEC4
+            | gl::UNSIGNED_INT
+            | gl::UNSIGNED_INT_VEC2
+            | gl::UNSIGNED_INT_VEC3
+            | gl::UNSIGNED_INT_VEC4
+    )
+}
+
+fn is_double_attribute(gl_type: GLenum) -> bool {
+    matches!(
+        gl_type,
+        gl::DOUBLE
+            | gl::DOUBLE_VEC2
+            | gl::DOUBLE_VEC3
+            | gl::DOUBLE_VEC4
+            | gl::DOUBLE_MAT2
+            | gl::DOUBLE_MAT3
+            | gl::DOUBLE_MAT4
+            | gl::DOUBLE_MAT2x3
+            | gl::DOUBLE_MAT2x4
+            | gl::DOUBLE_MAT3x2
+            | gl::DOUBLE_MAT3x4
+            | gl::DOUBLE_MAT4x2
+            | gl::DOUBLE_MAT4x3
+    )
+}
+
+fn is_integer_type(gl_type: GLenum) -> bool {
+    matches!(
+        gl_type,
+        gl::BYTE | gl::UNSIGNED_BYTE | gl::SHORT | gl::UNSIGNED_SHORT | gl::INT | gl::UNSIGNED_INT
+    )
+}
+
+//  Whether OpenGL accepts the kind, count and type of `element` together.
+fn is_valid_element(element: &VaoLayoutElement) -> bool {
+    let type_matches = match element.kind {
+        AttributeKind::Float { .. } => true,
+        AttributeKind::Integer => is_integer_type(element.gl_type),
+        AttributeKind::Double => element.gl_type == gl::DOUBLE,
+    };
+    let count_matches = match element.gl_type {
+        gl::INT_2_10_10_10_REV | gl::UNSIGNED_INT_2_10_10_10_REV => element.count == 4,
+        gl::UNSIGNED_INT_10F_11F_11F_REV => element.count == 3,
+        _ => (1..=4).contains(&element.count),
+    };
+    let size_known = size_of_gl_type(element.gl_type).is_some();
+
+    type_matches && count_matches && size_known
+}
+
+fn is_packed_type(gl_type: GLenum) -> bool {
+    matches!(
+        gl_type,
+        gl::INT_2_10_10_10_REV | gl::UNSIGNED_INT_2_10_10_10_REV | gl::UNSIGNED_INT_10F_11F_11F_REV
+    )
+}
+
+//  The size in bytes of `count` components of `gl_type`, `None` for types
+//  of unknown size.
+fn element_size(count: u8, gl_type: GLenum) -> Option<usize> {
+    let size = size_of_gl_type(gl_type)?;
+    if is_packed_type(gl_type) {
+        Some(size)
+    } else {
+        Some(count as usize * size)
+    }
 }
 
 fn apply_layout(layout: &VaoLayout) {
-    let mut offset = layout.stride;
-    let size = layout.layout.len();
-
-    for (index, elem) in layout.layout.iter().rev().enumerate() {
-        let index = size - index - 1;
-
-        offset -= size_of_gl_type(elem.gl_type).unwrap() * elem.count as usize;
-        gl_call!(gl::VertexAttribPointer(
-            index as u32,
-            elem.count as i32,
-            elem.gl_type,
-            elem.normalized,
-            layout.stride as i32,
-            offset as *const c_void,
-        ));
-        gl_call!(gl::EnableVertexAttribArray(index as u32));
+    for elem in layout.layout.iter() {
+        let stride = layout.stride as GLsizei;
+        let offset = elem.offset as *const c_void;
+        match elem.kind {
+            AttributeKind::Float { normalized } => gl_call!(gl::VertexAttribPointer(
+                elem.location,
+                elem.count as i32,
+                elem.gl_type,
+                normalized as GLboolean,
+                stride,
+                offset,
+            )),
+            AttributeKind::Integer => gl_call!(gl::VertexAttribIPointer(
+                elem.location,
+                elem.count as i32,
+                elem.gl_type,
+                stride,
+                offset,
+            )),
+            AttributeKind::Double => gl_call!(gl::VertexAttribLPointer(
+                elem.location,
+                elem.count as i32,
+                elem.gl_type,
+                stride,
+                offset,
+            )),
+        }
+        gl_call!(gl::VertexAttribDivisor(elem.location, layout.divisor));
+        gl_call!(gl::EnableVertexAttribArray(elem.location));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        backend::{record, GlCommand},
+        basic::{AttributeInfo, Vbo},
+        prelude::*,
+        GlContext,
+    };
+
+    fn attribute(name: &str, gl_type: GLenum, location: GLint) -> AttributeInfo {
+        AttributeInfo {
+            name: name.to_string(),
+            gl_type,
+            size: 1,
+            location,
+        }
+    }
+
+    #[repr(C)]
+    #[derive(Clone, Copy, Debug, Pod, Vertex)]
+    struct BasicVertex {
+        position: [f32; 3],
+        tex_coords: [f32; 2],
+    }
+
+    #[test]
+    fn apply_layout_sets_offsets_and_stride_of_every_field() {
+        let commands = record(|| {
+            let context = unsafe { GlContext::new() };
+            let vao = Vao::new(&context);
+            BasicVertex::layout().apply_layout(&vao);
+        });
+
+        let pointers: Vec<&GlCommand> = commands
+            .iter()
+            .filter(|command| matches!(command, GlCommand::VertexAttribPointer { .. }))
+            .collect();
+        assert_eq!(
+            pointers,
+            [
+                &GlCommand::VertexAttribPointer {
+                    index: 0,
+                    size: 3,
+                    type_: gl::FLOAT,
+                    normalized: gl::FALSE,
+                    stride: 20,
+                    pointer: std::ptr::null(),
+                },
+                &GlCommand::VertexAttribPointer {
+                    index: 1,
+                    size: 2,
+                    type_: gl::FLOAT,
+                    normalized: gl::FALSE,
+                    stride: 20,
+                    pointer: 12 as *const c_void,
+                },
+            ]
+        );
+        assert!(commands.contains(&GlCommand::BindVertexArray { array: 1 }));
+        assert!(commands.contains(&GlCommand::EnableVertexAttribArray { index: 0 }));
+        assert!(commands.contains(&GlCommand::EnableVertexAttribArray { index: 1 }));
+    }
+
+    #[test]
+    fn validate_accepts_a_matching_layout() {
+        let reflection = ShaderReflection::with_attributes(vec![
+            attribute("aPos", gl::FLOAT_VEC3, 0),
+            attribute("aUV", gl::FLOAT_VEC2, 1),
+        ]);
+
+        assert_eq!(BasicVertex::layout().validate(&reflection), Ok(()));
+    }
+
+    #[test]
+    fn validate_reports_component_count_mismatches() {
+        let reflection = ShaderReflection::with_attributes(vec![
+            attribute("aPos", gl::FLOAT_VEC3, 0),
+            attribute("aUV", gl::FLOAT_VEC2, 1),
+        ]);
+        let mut layout = VaoLayout::new();
+        layout.push_element_at(0, 3, AttribType::Float, false);
+        layout.push_element_at(1, 3, AttribType::Float, false);
+
+        assert_eq!(
+            layout.validate(&reflection),
+            Err(LayoutError {
+                mismatches: vec![LayoutMismatch::TypeMismatch {
+                    name: "aUV".to_string(),
+                    location: 1,
+                    attribute_type: gl::FLOAT_VEC2,
+                    count: 3,
+                    gl_type: gl::FLOAT,
+                    kind: AttributeKind::Float { normalized: false },
+                }],
+            })
+        );
+    }
+
+    #[test]
+    fn validate_reports_missing_and_duplicate_locations() {
+        let reflection = ShaderReflection::with_attributes(vec![
+            attribute("aPos", gl::FLOAT_VEC3, 0),
+            attribute("aNormal", gl::FLOAT_VEC3, 1),
+        ]);
+        let mut layout = VaoLayout::new();
+        layout.push_element_at(0, 3, AttribType::Float, false);
+        layout.push_element_at(0, 3, AttribType::Float, false);
+
+        assert_eq!(
+            layout.validate(&reflection),
+            Err(LayoutError {
+                mismatches: vec![
+                    LayoutMismatch::Missing {
+                        name: "aNormal".to_string(),
+                        location: 1,
+                        gl_type: gl::FLOAT_VEC3,
+                    },
+                    LayoutMismatch::DuplicateLocation { location: 0 },
+                ],
+            })
+        );
+    }
+
+    #[test]
+    fn validate_accepts_elements_the_shader_does_not_read() {
+        let reflection = ShaderReflection::with_attributes(vec![attribute("aPos", gl::FLOAT_VEC3, 0)]);
+
+        assert_eq!(BasicVertex::layout().validate(&reflection), Ok(()));
+    }
+
+    #[test]
+    fn validate_reports_elements_of_invalid_types() {
+        let reflection = ShaderReflection::with_attributes(vec![
+            attribute("aColor", gl::FLOAT_VEC4, 0),
+            attribute("aIndex", gl::INT, 1),
+        ]);
+        let mut layout = VaoLayout::with_stride(8);
+        layout.push_element_with_offset(
+            0,
+            0,
+            3,
+            AttribType::UnsignedInt10F11F11FRev,
+            AttributeKind::Float { normalized: false },
+        );
+        layout.push_element_with_offset(1, 4, 1, AttribType::Float, AttributeKind::Integer);
+
+        let error = layout.validate(&reflection).unwrap_err();
+        assert!(error.mismatches.contains(&LayoutMismatch::InvalidElement {
+            location: 1,
+            count: 1,
+            gl_type: gl::FLOAT,
+            kind: AttributeKind::Integer,
+        }));
+        assert!(!error
+            .mismatches
+            .iter()
+            .any(|mismatch| matches!(mismatch, LayoutMismatch::InvalidElement { location: 0, .. })));
+    }
+
+    #[test]
+    fn elements_of_unknown_size_are_reported_instead_of_panicking() {
+        let reflection = ShaderReflection::with_attributes(vec![
+            attribute("aPos", gl::FLOAT_VEC2, 0),
+            attribute("aWeight", gl::FLOAT, 1),
+        ]);
+        let mut layout = VaoLayout::new();
+        layout.push_element(2, AttribType::Fixed, false);
+        layout.push_element(1, AttribType::Raw(gl::FLOAT_VEC2), false);
+
+        assert_eq!(layout.stride(), 8);
+        assert_eq!(
+            layout.validate(&reflection),
+            Err(LayoutError {
+                mismatches: vec![LayoutMismatch::InvalidElement {
+                    location: 1,
+                    count: 1,
+                    gl_type: gl::FLOAT_VEC2,
+                    kind: AttributeKind::Float { normalized: false },
+                }],
+            })
+        );
+    }
+
+    #[test]
+    fn set_vertex_buffer_sets_the_format_of_every_kind() {
+        let mut layout = VaoLayout::new();
+        layout.push_element_at(0, 3, AttribType::UnsignedByte, true);
+        layout.push_integer_element(1, AttribType::Int);
+        layout.push_double_element(2);
+        layout.set_divisor(3);
+
+        let mut commands = Vec::new();
+        record(|| {
+            let context = unsafe { GlContext::new() };
+            let vao = Vao::new(&context);
+            let vbo: Vbo<u8> = Vbo::new(&context);
+            commands = record(|| vao.set_vertex_buffer(2, &vbo, &layout));
+        });
+
+        assert_eq!(
+            commands,
+            [
+                GlCommand::VertexArrayAttribFormat {
+                    vaobj: 1,
+                    attribindex: 0,
+                    size: 3,
+                    type_: gl::UNSIGNED_BYTE,
+                    normalized: gl::TRUE,
+                    relativeoffset: 0,
+                },
+                GlCommand::VertexArrayAttribBinding { vaobj: 1, attribindex: 0, bindingindex: 2 },
+                GlCommand::EnableVertexArrayAttrib { vaobj: 1, index: 0 },
+                GlCommand::VertexArrayAttribIFormat {
+                    vaobj: 1,
+                    attribindex: 1,
+                    size: 1,
+                    type_: gl::INT,
+                    relativeoffset: 3,
+                },
+                GlCommand::VertexArrayAttribBinding { vaobj: 1, attribindex: 1, bindingindex: 2 },
+                GlCommand::EnableVertexArrayAttrib { vaobj: 1, index: 1 },
+                GlCommand::VertexArrayAttribLFormat {
+                    vaobj: 1,
+                    attribindex: 2,
+                    size: 2,
+                    type_: gl::DOUBLE,
+                    relativeoffset: 7,
+                },
+                GlCommand::VertexArrayAttribBinding { vaobj: 1, attribindex: 2, bindingindex: 2 },
+                GlCommand::EnableVertexArrayAttrib { vaobj: 1, index: 2 },
+                GlCommand::VertexArrayBindingDivisor { vaobj: 1, bindingindex: 2, divisor: 3 },
+                GlCommand::VertexArrayVertexBuffer {
+                    vaobj: 1,
+                    bindingindex: 2,
+                    buffer: 2,
+                    offset: 0,
+                    stride: 23,
+                },
+            ]
+        );
     }
 }
@@ -0,0 +1,148 @@
+use gl::types::*;
+use super::AttributeKind;
+use crate::basic::glsl_type_name;
+use std::{error::Error, fmt};
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum LayoutMismatch {
+    /// The shader reads an attribute no element feeds.
+    Missing {
+        name: String,
+        location: GLuint,
+        gl_type: GLenum,
+    },
+    /// An element feeds a location the shader does not read (it may have been
+    /// optimized out). Only logged, never part of a `LayoutError`.
+    Extra {
+        location: GLuint,
+        count: u8,
+        gl_type: GLenum,
+    },
+    TypeMismatch {
+        name: String,
+        location: GLuint,
+        attribute_type: GLenum,
+        count: u8,
+        gl_type: GLenum,
+        kind: AttributeKind,
+    },
+    DuplicateLocation {
+        location: GLuint,
+    },
+    /// An element whose kind, count and type OpenGL rejects, e.g. an
+    /// `AttributeKind::Integer` element of `GL_FLOAT`s, or whose type has no
+    /// known size.
+    InvalidElement {
+        location: GLuint,
+        count: u8,
+        gl_type: GLenum,
+        kind: AttributeKind,
+    },
+}
+
+/// Every difference between a `VaoLayout` and the attributes of a program.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct LayoutError {
+    pub mismatches: Vec<LayoutMismatch>,
+}
+
+impl fmt::Display for LayoutMismatch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LayoutMismatch::Missing {
+                name,
+                location,
+                gl_type,
+            } => write!(
+                f,
+                "attribute {} {} at location {} is not fed by the layout",
+                glsl_type_name(*gl_type),
+                name,
+                location
+            ),
+            LayoutMismatch::Extra {
+                location,
+                count,
+                gl_type,
+            } => write!(
+                f,
+                "location {} ({} x {}) is not read by the shader",
+                location,
+                count,
+                gl_type_name(*gl_type)
+            ),
+            LayoutMismatch::TypeMismatch {
+                name,
+                location,
+                attribute_type,
+                count,
+                gl_type,
+                kind,
+            } => write!(
+                f,
+                "attribute {} {} at location {} is fed with {} x {} as {}",
+                glsl_type_name(*attribute_type),
+                name,
+                location,
+                count,
+                gl_type_name(*gl_type),
+                kind_name(*kind)
+            ),
+            LayoutMismatch::DuplicateLocation { location } => {
+                write!(f, "location {} is used by more than one element", location)
+            }
+            LayoutMismatch::InvalidElement {
+                location,
+                count,
+                gl_type,
+                kind,
+            } => write!(
+                f,
+                "location {} can not be fed with {} x {} as {}",
+                location,
+                count,
+                gl_type_name(*gl_type),
+                kind_name(*kind)
+            ),
+        }
+    }
+}
+
+impl fmt::Display for LayoutError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Vertex layout does not match the shader:")?;
+        for mismatch in self.mismatches.iter() {
+            write!(f, "\n    {}", mismatch)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl Error for LayoutError {}
+
+fn kind_name(kind: AttributeKind) -> &'static str {
+    match kind {
+        AttributeKind::Float { .. } => "float",
+        AttributeKind::Integer => "integer",
+        AttributeKind::Double => "double",
+    }
+}
+
+fn gl_type_name(gl_type: GLenum) -> &'static str {
+    match gl_type {
+        gl::FLOAT => "GL_FLOAT",
+        gl::DOUBLE => "GL_DOUBLE",
+        gl::INT => "GL_INT",
+        gl::UNSIGNED_INT => "GL_UNSIGNED_INT",
+        gl::SHORT => "GL_SHORT",
+        gl::UNSIGNED_SHORT => "GL_UNSIGNED_SHORT",
+        gl::BYTE => "GL_BYTE",
+        gl::UNSIGNED_BYTE => "GL_UNSIGNED_BYTE",
+        gl::HALF_FLOAT => "GL_HALF_FLOAT",
+        gl::INT_2_10_10_10_REV => "GL_INT_2_10_10_10_REV",
+        gl::UNSIGNED_INT_2_10_10_10_REV => "GL_UNSIGNED_INT_2_10_10_10_REV",
+        gl::UNSIGNED_INT_10F_11F_11F_REV => "GL_UNSIGNED_INT_10F_11F_11F_REV",
+        _ => "unknown type",
+    }
+}
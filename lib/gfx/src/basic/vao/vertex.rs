@@ -0,0 +1,99 @@
+use gl::types::*;
+use super::VaoLayout;
+use crate::basic::Pod;
+use cgmath::{Matrix2, Matrix3, Matrix4, Point2, Point3, Vector2, Vector3, Vector4};
+
+/// A vertex struct whose fields map to vertex attributes. Usually implemented
+/// with `#[derive(Pod, Vertex)]` on a `#[repr(C)]` struct.
+pub trait Vertex: Pod {
+    /// The layout of the struct, with every field at its real offset and the
+    /// size of the struct as stride.
+    fn layout() -> VaoLayout;
+}
+
+/// A scalar type that can be a component of a vertex attribute.
+pub trait VertexComponent: Copy {
+    const GL_TYPE: GLenum;
+}
+
+/// A field type of a vertex struct: one to four components of the same type,
+/// or a matrix of such columns.
+pub trait VertexAttribute: Copy {
+    /// Components per location.
+    const COUNT: u8;
+    const GL_TYPE: GLenum;
+    /// Consecutive locations taken, one per column for matrices.
+    const LOCATIONS: u32 = 1;
+}
+
+macro_rules! vertex_component {
+    ($($component:ty => $gl_type:path),+ $(,)?) => {
+        $(
+            impl VertexComponent for $component {
+                const GL_TYPE: GLenum = $gl_type;
+            }
+
+            impl VertexAttribute for $component {
+                const COUNT: u8 = 1;
+                const GL_TYPE: GLenum = $gl_type;
+            }
+        )+
+    };
+}
+
+vertex_component!(
+    f64 => gl::DOUBLE,
+    f32 => gl::FLOAT,
+    i32 => gl::INT,
+    u32 => gl::UNSIGNED_INT,
+    i16 => gl::SHORT,
+    u16 => gl::UNSIGNED_SHORT,
+    i8 => gl::BYTE,
+    u8 => gl::UNSIGNED_BYTE,
+);
+
+impl<T: VertexComponent, const N: usize> VertexAttribute for [T; N] {
+    const COUNT: u8 = {
+        assert!(N >= 1 && N <= 4, "a vertex attribute has one to four components");
+        N as u8
+    };
+    const GL_TYPE: GLenum = T::GL_TYPE;
+}
+
+//  cgmath vectors and points are `repr(C)`, so they are laid out like arrays.
+macro_rules! vertex_vector {
+    ($($vector:ident => $count:expr),+ $(,)?) => {
+        $(
+            impl<T: VertexComponent> VertexAttribute for $vector<T> {
+                const COUNT: u8 = $count;
+                const GL_TYPE: GLenum = T::GL_TYPE;
+            }
+        )+
+    };
+}
+
+vertex_vector!(
+    Vector2 => 2,
+    Vector3 => 3,
+    Vector4 => 4,
+    Point2 => 2,
+    Point3 => 3,
+);
+
+macro_rules! vertex_matrix {
+    ($($matrix:ident => $columns:expr),+ $(,)?) => {
+        $(
+            impl<T: VertexComponent> VertexAttribute for $matrix<T> {
+                const COUNT: u8 = $columns;
+                const GL_TYPE: GLenum = T::GL_TYPE;
+                const LOCATIONS: u32 = $columns;
+            }
+        )+
+    };
+}
+
+vertex_matrix!(
+    Matrix2 => 2,
+    Matrix3 => 3,
+    Matrix4 => 4,
+);
@@ -0,0 +1,125 @@
+use gl::types::*;
+use crate::{basic::untrack_object, gl_call};
+use std::{cell::Cell, ffi::CStr, marker::PhantomData, mem, sync::Mutex};
+
+thread_local! {
+    static IS_GL_THREAD: Cell<bool> = const { Cell::new(false) };
+}
+
+//  Objects dropped away from the gl thread, deleted by the next
+//  `GlContext::collect_garbage`.
+static DELETION_QUEUE: Mutex<Vec<(GLenum, GLuint)>> = Mutex::new(Vec::new());
+
+/// Proof that a gl context is current on this thread. Every call that creates
+/// a gl object asks for one, and since it is `!Send` the objects can only be
+/// created on the gl thread.
+///
+/// The objects themselves can be moved to other threads. When they are
+/// dropped there, their deletion is queued until `collect_garbage` runs on
+/// the gl thread (`Window` does it after every frame).
+#[derive(Clone)]
+pub struct GlContext {
+    _not_send: PhantomData<*const ()>,
+}
+
+impl GlContext {
+    /// # Safety
+    ///
+    /// A context must be current on this thread and the gl functions must be
+    /// loaded for as long as any object created with the token is alive.
+    pub unsafe fn new() -> GlContext {
+        IS_GL_THREAD.with(|is_gl_thread| is_gl_thread.set(true));
+
+        GlContext {
+            _not_send: PhantomData,
+        }
+    }
+
+    /// Deletes the objects that were dropped on other threads since the last
+    /// call. Returns how many were deleted.
+    pub fn collect_garbage(&self) -> usize {
+        let queue = mem::take(&mut *DELETION_QUEUE.lock().unwrap());
+        for (namespace, name) in queue.iter() {
+            delete_now(*namespace, *name);
+        }
+
+        queue.len()
+    }
+
+    /// Whether the context is at least OpenGL `major.minor` or exposes
+    /// `extension` (e.g. `"GL_ARB_base_instance"`).
+    pub fn supports(&self, major: GLint, minor: GLint, extension: &str) -> bool {
+        supports(major, minor, extension)
+    }
+}
+
+pub(crate) fn supports(major: GLint, minor: GLint, extension: &str) -> bool {
+    let (mut context_major, mut context_minor) = (0, 0);
+    gl_call!(gl::GetIntegerv(gl::MAJOR_VERSION, &mut context_major));
+    gl_call!(gl::GetIntegerv(gl::MINOR_VERSION, &mut context_minor));
+    if (context_major, context_minor) >= (major, minor) {
+        return true;
+    }
+
+    let mut extension_count = 0;
+    gl_call!(gl::GetIntegerv(gl::NUM_EXTENSIONS, &mut extension_count));
+    (0..extension_count as GLuint).any(|index| {
+        let name;
+        gl_call!(name = gl::GetStringi(gl::EXTENSIONS, index));
+        !name.is_null() && unsafe { CStr::from_ptr(name as *const _) }.to_bytes() == extension.as_bytes()
+    })
+}
+
+/// Deletes a gl object when called on the gl thread and queues it for the
+/// next `GlContext::collect_garbage` otherwise.
+pub(crate) fn delete_object(namespace: GLenum, name: GLuint) {
+    if IS_GL_THREAD.with(Cell::get) {
+        delete_now(namespace, name);
+    } else {
+        DELETION_QUEUE.lock().unwrap().push((namespace, name));
+    }
+}
+
+//  Untracks the object here rather than in `delete_object`, as the live
+//  objects are recorded per thread and only the gl thread tracked it.
+fn delete_now(namespace: GLenum, name: GLuint) {
+    untrack_object(namespace, name);
+
+    match namespace {
+        gl::BUFFER => gl_call!(gl::DeleteBuffers(1, &name)),
+        gl::VERTEX_ARRAY => gl_call!(gl::DeleteVertexArrays(1, &name)),
+        gl::TEXTURE => gl_call!(gl::DeleteTextures(1, &name)),
+        gl::PROGRAM => gl_call!(gl::DeleteProgram(name)),
+        _ => unreachable!("gl objects of namespace {:#x} are not deleted through the context", namespace),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        backend::{record, GlCommand},
+        basic::{ArrayTarget, Buffer},
+    };
+    use std::thread;
+
+    #[test]
+    fn objects_dropped_on_other_threads_are_deleted_by_collect_garbage() {
+        let mut buffer_id = 0;
+        let commands = record(|| {
+            let context = unsafe { GlContext::new() };
+            let buffer: Buffer<f32, ArrayTarget> = Buffer::new(&context);
+            buffer_id = buffer.id();
+
+            thread::spawn(move || drop(buffer)).join().unwrap();
+            context.collect_garbage();
+        });
+
+        //  The deletion queue is shared by every thread, so other objects may
+        //  be collected as well.
+        assert!(commands.iter().any(|command| match command {
+            GlCommand::DeleteBuffers { n: 1, buffers } => buffer_id == unsafe { **buffers },
+            _ => false,
+        }));
+    }
+}
@@ -1,4 +1,8 @@
 pub use macros::EboObject;
+pub use macros::Pod;
+pub use macros::Std140;
 pub use macros::TextureObject;
+pub use macros::Uniforms;
 pub use macros::VaoObject;
 pub use macros::VboObject;
+pub use macros::Vertex;
@@ -0,0 +1,183 @@
+use image::{Rgba, RgbaImage};
+use std::{
+    env, fmt, fs,
+    path::{Path, PathBuf},
+};
+
+/// Set this environment variable to (re)write the reference images instead of
+/// comparing against them.
+pub const UPDATE_GOLDEN_VAR: &str = "UPDATE_GOLDEN";
+
+/// Where `assert_golden` writes the actual frame and the diff image of a
+/// failed comparison. Defaults to `target/golden`.
+pub const GOLDEN_OUTPUT_VAR: &str = "GOLDEN_OUTPUT_DIR";
+
+#[derive(Clone, Copy, Debug)]
+pub struct GoldenTolerance {
+    /// The largest per-channel difference that still counts as equal.
+    pub max_channel_difference: u8,
+    /// How many pixels may exceed `max_channel_difference`.
+    pub max_differing_pixels: usize,
+}
+
+impl Default for GoldenTolerance {
+    fn default() -> Self {
+        GoldenTolerance {
+            max_channel_difference: 2,
+            max_differing_pixels: 0,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum GoldenError {
+    MissingReference(PathBuf),
+    InvalidReference(PathBuf, image::ImageError),
+    SizeMismatch {
+        expected: (u32, u32),
+        found: (u32, u32),
+    },
+    Mismatch {
+        differing_pixels: usize,
+        max_difference: u8,
+        diff: RgbaImage,
+    },
+}
+
+impl fmt::Display for GoldenError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GoldenError::MissingReference(path) => write!(
+                f,
+                "Reference image {} does not exist (run with {}=1 to create it)",
+                path.display(),
+                UPDATE_GOLDEN_VAR
+            ),
+            GoldenError::InvalidReference(path, error) => {
+                write!(f, "Could not read reference image {}: {}", path.display(), error)
+            }
+            GoldenError::SizeMismatch { expected, found } => write!(
+                f,
+                "Frame is {}x{}, but the reference is {}x{}",
+                found.0, found.1, expected.0, expected.1
+            ),
+            GoldenError::Mismatch {
+                differing_pixels,
+                max_difference,
+                ..
+            } => write!(
+                f,
+                "{} pixels differ from the reference (max channel difference {})",
+                differing_pixels, max_difference
+            ),
+        }
+    }
+}
+
+impl std::error::Error for GoldenError {}
+
+/// Compares `actual` against the PNG at `reference`.
+pub fn compare_golden(
+    actual: &RgbaImage,
+    reference: &Path,
+    tolerance: GoldenTolerance,
+) -> Result<(), GoldenError> {
+    if !reference.exists() {
+        return Err(GoldenError::MissingReference(reference.to_path_buf()));
+    }
+    let expected = image::open(reference)
+        .map_err(|error| GoldenError::InvalidReference(reference.to_path_buf(), error))?
+        .to_rgba8();
+
+    compare_images(actual, &expected, tolerance)
+}
+
+pub fn compare_images(
+    actual: &RgbaImage,
+    expected: &RgbaImage,
+    tolerance: GoldenTolerance,
+) -> Result<(), GoldenError> {
+    if actual.dimensions() != expected.dimensions() {
+        return Err(GoldenError::SizeMismatch {
+            expected: expected.dimensions(),
+            found: actual.dimensions(),
+        });
+    }
+
+    let mut diff = RgbaImage::new(actual.width(), actual.height());
+    let mut differing_pixels = 0;
+    let mut max_difference = 0;
+
+    for ((actual_pixel, expected_pixel), diff_pixel) in actual
+        .pixels()
+        .zip(expected.pixels())
+        .zip(diff.pixels_mut())
+    {
+        let difference = actual_pixel
+            .0
+            .iter()
+            .zip(expected_pixel.0.iter())
+            .map(|(a, b)| (*a as i16 - *b as i16).unsigned_abs() as u8)
+            .max()
+            .unwrap_or(0);
+        max_difference = max_difference.max(difference);
+
+        //  Differing pixels are red, everything else is a faded copy of the
+        //  frame so the failure can be located.
+        *diff_pixel = if difference > tolerance.max_channel_difference {
+            differing_pixels += 1;
+            Rgba([255, 0, 0, 255])
+        } else {
+            let [r, g, b, _] = actual_pixel.0;
+            let luma = ((r as u16 + g as u16 + b as u16) / 3 / 4) as u8;
+            Rgba([luma, luma, luma, 255])
+        };
+    }
+
+    if differing_pixels > tolerance.max_differing_pixels {
+        return Err(GoldenError::Mismatch {
+            differing_pixels,
+            max_difference,
+            diff,
+        });
+    }
+
+    Ok(())
+}
+
+/// Test helper: panics when `actual` does not match the reference. On failure
+/// the actual frame and a diff image are written to `GOLDEN_OUTPUT_DIR`. With
+/// `UPDATE_GOLDEN` set the reference is overwritten instead.
+pub fn assert_golden(actual: &RgbaImage, reference: &Path, tolerance: GoldenTolerance) {
+    if env::var_os(UPDATE_GOLDEN_VAR).is_some() {
+        if let Some(parent) = reference.parent() {
+            fs::create_dir_all(parent).unwrap();
+        }
+        actual.save(reference).unwrap();
+        log::info!("Updated reference image {}", reference.display());
+        return;
+    }
+
+    if let Err(error) = compare_golden(actual, reference, tolerance) {
+        let output_dir = env::var_os(GOLDEN_OUTPUT_VAR)
+            .map(PathBuf::from)
+            .unwrap_or_else(|| PathBuf::from("target/golden"));
+        let name = reference
+            .file_stem()
+            .map(|stem| stem.to_string_lossy().into_owned())
+            .unwrap_or_else(|| "frame".to_string());
+
+        fs::create_dir_all(&output_dir).unwrap();
+        let actual_path = output_dir.join(format!("{}.actual.png", name));
+        actual.save(&actual_path).unwrap();
+
+        let mut message = format!("{}\n    actual: {}", error, actual_path.display());
+        if let GoldenError::Mismatch { diff, .. } = &error {
+            let diff_path = output_dir.join(format!("{}.diff.png", name));
+            diff.save(&diff_path).unwrap();
+            message += &format!("\n    diff:   {}", diff_path.display());
+        }
+
+        panic!("Golden image {} does not match: {}", reference.display(), message);
+    }
+}
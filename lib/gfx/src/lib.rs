@@ -1,26 +1,33 @@
+pub mod backend;
 pub mod basic;
 pub mod derives;
+pub mod golden;
 pub mod prelude;
 
+mod context;
 mod mesh;
 mod mesh_builder;
 mod pipeline;
 
+pub use context::GlContext;
+pub(crate) use context::delete_object;
 pub use mesh::Mesh;
 pub use mesh_builder::MeshBuilder;
-pub use pipeline::Pipeline;
+pub use pipeline::{
+    BlendFactor, CullFace, DepthFunc, FrontFace, Pipeline, PipelineStates, PolygonMode,
+};
+pub use macros::shader_files;
 
 #[macro_export]
 macro_rules! gl_call {
-    ($l:expr) => {
-        unsafe {
-            use $crate::basic::get_gl_error_string;
-
-            $l;
-
-            while let Some(message) = get_gl_error_string(gl::GetError()) {
-                log::warn!("{}", message);
-            }
+    ($result:ident = gl::$name:ident($($arg:expr),* $(,)?)) => {
+        $result = $crate::gl_call!(gl::$name($($arg),*))
+    };
+    (gl::$name:ident($($arg:expr),* $(,)?)) => {
+        match $crate::backend::dispatch::$name::args($($arg),*) {
+            args => $crate::backend::with(|backend| unsafe {
+                $crate::backend::dispatch::$name::call(backend, args)
+            }),
         }
     };
 }
@@ -1,37 +1,93 @@
 use crate::{
-    basic::{Bindable, Drawable, Ebo, Vbo},
-    gl_call,
+    basic::{cast_slice, Bindable, BufferUsage, Drawable, Ebo, Index, IndexType, Pod, Vbo},
+    context::supports,
+    gl_call, GlContext,
 };
 
+/// Vertices drawn through an index buffer of `u8`, `u16` or `u32` indices.
 pub struct Mesh {
     vertices: Vbo,
-    indices: Ebo,
+    indices: Ebo<u8>,
+    index_type: IndexType,
     indices_len: usize,
+    //  Queried once here rather than on every `draw_instanced_base_instance`.
+    base_instance: bool,
 }
 
 impl Mesh {
-    pub fn new(vertices: Vbo, indices: Ebo, indices_len: usize) -> Mesh {
+    pub fn new<I: Index>(vertices: Vbo, indices: Ebo<I>) -> Mesh {
         Mesh {
             vertices,
-            indices,
-            indices_len,
+            indices_len: indices.len(),
+            indices: indices.into_bytes(),
+            index_type: I::INDEX_TYPE,
+            base_instance: supports(4, 2, "GL_ARB_base_instance"),
         }
     }
 
-    pub fn from_data<T>(vertices: Vec<T>, indices: Vec<u32>) -> Mesh {
-        let vbo = Vbo::new();
-        vbo.add_data(vertices.as_slice(), gl::STATIC_DRAW);
-        //vbo.add_raw_data(vertices.as_ptr() as *const std::ffi::c_void, vertices.len() * std::mem::size_of::<T>(), gl::STATIC_DRAW);
+    pub fn from_data<T: Pod, I: Index>(
+        context: &GlContext,
+        vertices: Vec<T>,
+        indices: Vec<I>,
+    ) -> Mesh {
+        let vbo = Vbo::with_data(context, cast_slice(&vertices), BufferUsage::StaticDraw);
+        let ebo = Ebo::with_data(context, &indices, BufferUsage::StaticDraw);
 
-        let ebo = Ebo::new();
-        //ebo.add_raw_data(indices.as_ptr() as *const std::ffi::c_void, indices.len() * std::mem::size_of::<T>(), gl::STATIC_DRAW);
-        ebo.add_data(indices.as_slice(), gl::STATIC_DRAW);
+        Mesh::new(vbo, ebo)
+    }
 
-        Mesh {
-            vertices: vbo,
-            indices: ebo,
-            indices_len: indices.len(),
+    pub fn index_type(&self) -> IndexType {
+        self.index_type
+    }
+
+    /// Draws `instance_count` instances of the mesh, see `InstanceBuffer`
+    /// for per-instance attributes.
+    pub fn draw_instanced(&self, instance_count: usize) {
+        gl_call!(gl::DrawElementsInstanced(
+            gl::TRIANGLES,
+            self.indices_len as i32,
+            self.index_type.gl_type(),
+            std::ptr::null(),
+            instance_count as i32
+        ));
+    }
+
+    /// Like `draw_instanced`, with per-instance attributes starting at
+    /// instance `base_instance` of their buffers. Needs OpenGL 4.2 or
+    /// ARB_base_instance. Without either, a `base_instance` of 0 falls back
+    /// to `draw_instanced` and any other draws nothing and logs an error.
+    pub fn draw_instanced_base_instance(&self, instance_count: usize, base_instance: u32) {
+        if !self.base_instance {
+            if base_instance == 0 {
+                self.draw_instanced(instance_count);
+            } else {
+                log::error!(
+                    "drawing from base instance {} needs OpenGL 4.2 or GL_ARB_base_instance",
+                    base_instance
+                );
+            }
+            return;
         }
+
+        gl_call!(gl::DrawElementsInstancedBaseInstance(
+            gl::TRIANGLES,
+            self.indices_len as i32,
+            self.index_type.gl_type(),
+            std::ptr::null(),
+            instance_count as i32,
+            base_instance
+        ));
+    }
+
+    /// Draws the indices as patches, for programs with tessellation stages
+    /// (see `PipelineStates::patch_vertices`).
+    pub fn draw_patches(&self) {
+        gl_call!(gl::DrawElements(
+            gl::PATCHES,
+            self.indices_len as i32,
+            self.index_type.gl_type(),
+            std::ptr::null()
+        ));
     }
 }
 
@@ -52,8 +108,52 @@ impl Drawable for Mesh {
         gl_call!(gl::DrawElements(
             gl::TRIANGLES,
             self.indices_len as i32,
-            gl::UNSIGNED_INT,
+            self.index_type.gl_type(),
             std::ptr::null()
         ));
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::backend::{record, GlCommand};
+
+    //  The recording backend reports version 0.0 without extensions.
+    #[test]
+    fn draw_instanced_base_instance_needs_base_instance_support() {
+        let commands = record(|| {
+            let context = unsafe { GlContext::new() };
+            let mesh = Mesh::from_data(&context, vec![[0.0f32; 3]; 3], vec![0u8, 1, 2]);
+            mesh.draw_instanced_base_instance(2, 0);
+            mesh.draw_instanced_base_instance(2, 1);
+        });
+
+        //  MAJOR_VERSION, MINOR_VERSION and NUM_EXTENSIONS, once for both draws.
+        let queries = commands
+            .iter()
+            .filter(|command| matches!(command, GlCommand::GetIntegerv { .. }))
+            .count();
+        assert_eq!(queries, 3);
+
+        let draws: Vec<&GlCommand> = commands
+            .iter()
+            .filter(|command| {
+                matches!(
+                    command,
+                    GlCommand::DrawElementsInstanced { .. } | GlCommand::DrawElementsInstancedBaseInstance { .. }
+                )
+            })
+            .collect();
+        assert_eq!(
+            draws,
+            [&GlCommand::DrawElementsInstanced {
+                mode: gl::TRIANGLES,
+                count: 3,
+                type_: gl::UNSIGNED_BYTE,
+                indices: std::ptr::null(),
+                instancecount: 2,
+            }]
+        );
+    }
+}
@@ -1,10 +1,13 @@
 use std::fmt::Debug;
 
-use crate::Mesh;
+use crate::{
+    basic::{IndexType, Pod},
+    GlContext, Mesh,
+};
 
 pub struct MeshBuilder<T>
 where
-    T: Sized + Copy + Debug,
+    T: Pod + Debug,
 {
     vertices: Vec<T>,
     indices: Vec<u32>,
@@ -13,7 +16,7 @@ where
 
 impl<T> MeshBuilder<T>
 where
-    T: Sized + Copy + Debug,
+    T: Pod + Debug,
 {
     pub fn new() -> MeshBuilder<T> {
         MeshBuilder {
@@ -44,9 +47,57 @@ where
         self.indices_used += 4;
     }
 
-    pub fn build(self) -> Mesh {
+    /// Builds the mesh with the smallest index type that can hold its largest
+    /// index (see `IndexType::for_vertex_count`).
+    pub fn build(self, context: &GlContext) -> Mesh {
         //log::info!("{:?}\n{:?}", self.vertices, self.indices);
 
-        Mesh::from_data(self.vertices, self.indices)
+        let addressed = self.indices.iter().max().map_or(0, |index| *index as usize + 1);
+        match IndexType::for_vertex_count(addressed) {
+            IndexType::U8 => {
+                let indices: Vec<u8> = self.indices.iter().map(|index| *index as u8).collect();
+                Mesh::from_data(context, self.vertices, indices)
+            }
+            IndexType::U16 => {
+                let indices: Vec<u16> = self.indices.iter().map(|index| *index as u16).collect();
+                Mesh::from_data(context, self.vertices, indices)
+            }
+            IndexType::U32 => Mesh::from_data(context, self.vertices, self.indices),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        backend::{record, GlCommand},
+        prelude::Drawable,
+    };
+
+    #[test]
+    fn build_uploads_vertices_and_indices() {
+        let commands = record(|| {
+            let context = unsafe { GlContext::new() };
+            let mut builder: MeshBuilder<[f32; 3]> = MeshBuilder::new();
+            builder.push_quad(&[[0.0; 3]; 4]);
+            builder.push_triangle(&[[1.0; 3]; 3]);
+            builder.build(&context).draw();
+        });
+
+        let uploads: Vec<_> = commands
+            .iter()
+            .filter_map(|command| match command {
+                GlCommand::BufferData { target, size, .. } => Some((*target, *size)),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(uploads, [(gl::ARRAY_BUFFER, 7 * 12), (gl::ELEMENT_ARRAY_BUFFER, 9)]);
+        assert!(commands.contains(&GlCommand::DrawElements {
+            mode: gl::TRIANGLES,
+            count: 9,
+            type_: gl::UNSIGNED_BYTE,
+            indices: std::ptr::null(),
+        }));
     }
 }
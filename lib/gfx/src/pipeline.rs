@@ -1,6 +1,7 @@
 use gl::types::*;
-use crate::{basic::{Bindable, Shader, ShaderObject, ShaderUniform, Vao, VaoObject}, derives::VaoObject, gl_call};
-use std::path::Path;
+use crate::{basic::{Bindable, InstanceBuffer, Labelable, LayoutError, Shader, ShaderError, ShaderObject, ShaderUniform, UniformInfo, Vao, VaoLayout, VaoObject, Vertex, VertexStream}, derives::VaoObject, gl_call, GlContext};
+use crate::basic::gl_enum;
+use std::{any::TypeId, path::Path, rc::Rc};
 
 #[derive(VaoObject)]
 pub struct Pipeline {
@@ -10,12 +11,29 @@ pub struct Pipeline {
 }
 
 impl Pipeline {
-    pub fn new(shader_vertex_source: &Path, shader_fragment_source: &Path) -> Pipeline {
-        Pipeline {
-            vao: Vao::new(),
-            shader: Shader::new(shader_vertex_source, shader_fragment_source),
+    pub fn new(
+        context: &GlContext,
+        shader_vertex_source: &Path,
+        shader_fragment_source: &Path,
+    ) -> Result<Pipeline, ShaderError> {
+        Ok(Pipeline {
+            vao: Vao::new(context),
+            shader: Shader::new(context, shader_vertex_source, shader_fragment_source)?,
             states: PipelineStates::default(),
-        }
+        })
+    }
+
+    /// See `Shader::from_sources`.
+    pub fn from_sources(
+        context: &GlContext,
+        vertex_code: &str,
+        fragment_code: &str,
+    ) -> Result<Pipeline, ShaderError> {
+        Ok(Pipeline {
+            vao: Vao::new(context),
+            shader: Shader::from_sources(context, vertex_code, fragment_code)?,
+            states: PipelineStates::default(),
+        })
     }
 
     pub fn from_existing(shader: Shader, vao: Vao) -> Pipeline {
@@ -30,7 +48,7 @@ impl Pipeline {
         match self.states.depth_test {
             Some(mode) => {
                 gl_call!(gl::Enable(gl::DEPTH_TEST));
-                gl_call!(gl::DepthFunc(mode));
+                gl_call!(gl::DepthFunc(mode.gl_type()));
             },
             None => {
                 gl_call!(gl::Disable(gl::DEPTH_TEST));
@@ -40,7 +58,7 @@ impl Pipeline {
         match self.states.blend {
             Some((sfactor, dfactor)) => {
                 gl_call!(gl::Enable(gl::BLEND));
-                gl_call!(gl::BlendFunc(sfactor, dfactor));
+                gl_call!(gl::BlendFunc(sfactor.gl_type(), dfactor.gl_type()));
             },
             None => {
                 gl_call!(gl::Disable(gl::BLEND));
@@ -50,15 +68,72 @@ impl Pipeline {
         match self.states.cull_face {
             Some((face, mode)) => {
                 gl_call!(gl::Enable(gl::CULL_FACE));
-                gl_call!(gl::CullFace(face));
-                gl_call!(gl::FrontFace(mode));
+                gl_call!(gl::CullFace(face.gl_type()));
+                gl_call!(gl::FrontFace(mode.gl_type()));
             },
             None => {
                 gl_call!(gl::Disable(gl::CULL_FACE));
             },
         }
 
-        gl_call!(gl::PolygonMode(gl::FRONT_AND_BACK, self.states.polygon_mode));
+        gl_call!(gl::PolygonMode(gl::FRONT_AND_BACK, self.states.polygon_mode.gl_type()));
+
+        if let Some(patch_vertices) = self.states.patch_vertices {
+            gl_call!(gl::PatchParameteri(gl::PATCH_VERTICES, patch_vertices));
+        }
+    }
+
+    /// See `Shader::watch`.
+    pub fn watch(&mut self) {
+        self.shader.watch();
+    }
+
+    /// See `Shader::reload_if_changed`.
+    pub fn reload_if_changed(&mut self) -> Result<bool, ShaderError> {
+        self.shader.reload_if_changed()
+    }
+
+    /// See `Shader::bind_uniform_block`.
+    pub fn bind_uniform_block(&mut self, block_name: &str, binding: GLuint) {
+        self.shader.bind_uniform_block(block_name, binding);
+    }
+
+    /// Checks `layout` against the attributes of the program and applies it
+    /// to the pipeline's vao if they match. Elements the program does not
+    /// read are logged and applied anyway.
+    pub fn apply_layout(&self, layout: &VaoLayout) -> Result<(), LayoutError> {
+        layout.validate(self.shader.reflection())?;
+        layout.apply_layout(&self.vao);
+
+        Ok(())
+    }
+
+    /// Like `apply_layout`, with the attributes of `vertex_layout` read from
+    /// the bound `Vbo` and the ones of `T` read per instance from `instances`.
+    pub fn apply_instanced_layout<T: Vertex>(
+        &self,
+        vertex_layout: &VaoLayout,
+        instances: &InstanceBuffer<T>,
+    ) -> Result<(), LayoutError> {
+        let instance_layout = instances.layout();
+        VaoLayout::validate_combined(&[vertex_layout, &instance_layout], self.shader.reflection())?;
+        vertex_layout.apply_layout(&self.vao);
+        instances.apply_layout(&self.vao);
+
+        Ok(())
+    }
+
+    /// Checks the layouts of several vertex buffers together against the
+    /// attributes of the program, then binds stream `i` to buffer binding
+    /// slot `i` of the pipeline's vao (see `Vao::set_vertex_buffer`).
+    pub fn apply_streams(&self, streams: &[VertexStream]) -> Result<(), LayoutError> {
+        let layouts: Vec<&VaoLayout> = streams.iter().map(|stream| stream.layout()).collect();
+        VaoLayout::validate_combined(&layouts, self.shader.reflection())?;
+        for (binding, stream) in streams.iter().enumerate() {
+            self.vao.set_stream(binding as GLuint, stream);
+        }
+
+        Ok(())
     }
 
     pub fn vao(&self) -> &Vao {
@@ -83,6 +158,13 @@ impl Bindable for Pipeline {
     }
 }
 
+impl Labelable for Pipeline {
+    fn label(&self, label: &str) {
+        self.vao.label(label);
+        self.shader.label(label);
+    }
+}
+
 impl ShaderObject for Pipeline {
     #[inline]
     fn set_uniform<T: ShaderUniform>(&mut self, uniform_name: &str, value: T) {
@@ -93,13 +175,93 @@ impl ShaderObject for Pipeline {
     fn get_uniform_location(&mut self, uniform_name: &str) -> GLint {
         self.shader.get_uniform_location(uniform_name)
     }
+
+    #[inline]
+    fn uniform_info(&self, uniform_name: &str) -> Option<&UniformInfo> {
+        self.shader.uniform_info(uniform_name)
+    }
+
+    #[inline]
+    fn cached_uniform_locations(&self, key: TypeId) -> Option<Rc<[GLint]>> {
+        self.shader.cached_uniform_locations(key)
+    }
+
+    #[inline]
+    fn cache_uniform_locations(&mut self, key: TypeId, locations: Rc<[GLint]>) {
+        self.shader.cache_uniform_locations(key, locations);
+    }
+}
+
+gl_enum! {
+    /// The comparison a fragment's depth has to pass against the depth buffer.
+    pub enum DepthFunc {
+        Never => gl::NEVER,
+        Less => gl::LESS,
+        Equal => gl::EQUAL,
+        LessEqual => gl::LEQUAL,
+        Greater => gl::GREATER,
+        NotEqual => gl::NOTEQUAL,
+        GreaterEqual => gl::GEQUAL,
+        Always => gl::ALWAYS,
+    }
+}
+
+gl_enum! {
+    /// A factor the source or destination color is multiplied by when
+    /// blending.
+    pub enum BlendFactor {
+        Zero => gl::ZERO,
+        One => gl::ONE,
+        SrcColor => gl::SRC_COLOR,
+        OneMinusSrcColor => gl::ONE_MINUS_SRC_COLOR,
+        DstColor => gl::DST_COLOR,
+        OneMinusDstColor => gl::ONE_MINUS_DST_COLOR,
+        SrcAlpha => gl::SRC_ALPHA,
+        OneMinusSrcAlpha => gl::ONE_MINUS_SRC_ALPHA,
+        DstAlpha => gl::DST_ALPHA,
+        OneMinusDstAlpha => gl::ONE_MINUS_DST_ALPHA,
+        ConstantColor => gl::CONSTANT_COLOR,
+        OneMinusConstantColor => gl::ONE_MINUS_CONSTANT_COLOR,
+        ConstantAlpha => gl::CONSTANT_ALPHA,
+        OneMinusConstantAlpha => gl::ONE_MINUS_CONSTANT_ALPHA,
+        SrcAlphaSaturate => gl::SRC_ALPHA_SATURATE,
+    }
+}
+
+gl_enum! {
+    /// The faces that are culled.
+    pub enum CullFace {
+        Front => gl::FRONT,
+        Back => gl::BACK,
+        FrontAndBack => gl::FRONT_AND_BACK,
+    }
+}
+
+gl_enum! {
+    /// The winding order of front facing triangles.
+    pub enum FrontFace {
+        Clockwise => gl::CW,
+        CounterClockwise => gl::CCW,
+    }
+}
+
+gl_enum! {
+    /// How polygons are rasterized.
+    pub enum PolygonMode {
+        Point => gl::POINT,
+        Line => gl::LINE,
+        Fill => gl::FILL,
+    }
 }
 
 pub struct PipelineStates {
-    pub depth_test: Option<GLenum>,
-    pub blend: Option<(GLenum, GLenum)>,
-    pub cull_face: Option<(GLenum, GLenum)>,
-    pub polygon_mode: GLenum,
+    pub depth_test: Option<DepthFunc>,
+    /// The source and destination factors.
+    pub blend: Option<(BlendFactor, BlendFactor)>,
+    pub cull_face: Option<(CullFace, FrontFace)>,
+    pub polygon_mode: PolygonMode,
+    /// Vertices per patch, for programs with tessellation stages.
+    pub patch_vertices: Option<GLint>,
 
     //  There will be more...
 }
@@ -110,7 +272,70 @@ impl Default for PipelineStates {
             depth_test: None,
             blend: None,
             cull_face: None,
-            polygon_mode: gl::FILL,
+            polygon_mode: PolygonMode::Fill,
+            patch_vertices: None,
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::backend::{record, GlCommand};
+
+    const VERTEX: &str = "#version 330 core\nvoid main() { gl_Position = vec4(0.0); }\n";
+    const FRAGMENT: &str = "#version 330 core\nout vec4 color;\nvoid main() { color = vec4(1.0); }\n";
+
+    #[test]
+    fn update_states_enables_and_configures_every_state() {
+        record(|| {
+            let context = unsafe { GlContext::new() };
+            let mut pipeline = Pipeline::from_sources(&context, VERTEX, FRAGMENT).unwrap();
+            pipeline.states = PipelineStates {
+                depth_test: Some(DepthFunc::LessEqual),
+                blend: Some((BlendFactor::SrcAlpha, BlendFactor::OneMinusSrcAlpha)),
+                cull_face: Some((CullFace::Back, FrontFace::CounterClockwise)),
+                polygon_mode: PolygonMode::Line,
+                patch_vertices: Some(3),
+            };
+
+            let commands = record(|| pipeline.update_states());
+            assert_eq!(
+                commands,
+                [
+                    GlCommand::Enable { cap: gl::DEPTH_TEST },
+                    GlCommand::DepthFunc { func: gl::LEQUAL },
+                    GlCommand::Enable { cap: gl::BLEND },
+                    GlCommand::BlendFunc {
+                        sfactor: gl::SRC_ALPHA,
+                        dfactor: gl::ONE_MINUS_SRC_ALPHA,
+                    },
+                    GlCommand::Enable { cap: gl::CULL_FACE },
+                    GlCommand::CullFace { mode: gl::BACK },
+                    GlCommand::FrontFace { mode: gl::CCW },
+                    GlCommand::PolygonMode { face: gl::FRONT_AND_BACK, mode: gl::LINE },
+                    GlCommand::PatchParameteri { pname: gl::PATCH_VERTICES, value: 3 },
+                ]
+            );
+        });
+    }
+
+    #[test]
+    fn update_states_disables_unset_states() {
+        record(|| {
+            let context = unsafe { GlContext::new() };
+            let pipeline = Pipeline::from_sources(&context, VERTEX, FRAGMENT).unwrap();
+
+            let commands = record(|| pipeline.update_states());
+            assert_eq!(
+                commands,
+                [
+                    GlCommand::Disable { cap: gl::DEPTH_TEST },
+                    GlCommand::Disable { cap: gl::BLEND },
+                    GlCommand::Disable { cap: gl::CULL_FACE },
+                    GlCommand::PolygonMode { face: gl::FRONT_AND_BACK, mode: gl::FILL },
+                ]
+            );
+        });
+    }
+}
@@ -1,5 +1,9 @@
 pub use crate::{
-    basic::{Bindable, Drawable, EboObject, ShaderObject, TextureObject, VaoObject, VboObject},
+    basic::{
+        AttribType, AttributeKind, Bindable, Drawable, EboObject, Labelable, Pod, ShaderObject,
+        ShaderUniform, Std140, TextureObject, Uniforms, VaoObject, VaoLayout, VboObject, Vertex,
+        VertexAttribute,
+    },
     derives::*,
     gl_call,
 };
@@ -31,17 +31,35 @@ impl syn::parse::Parse for Args {
     }
 }
 
+fn is_call_to(call: &syn::ExprCall, name: &str) -> bool {
+    match &*call.func {
+        syn::Expr::Path(path) => path.path.is_ident(name),
+        _ => false,
+    }
+}
+
 #[proc_macro_attribute]
 pub fn main_app(args: TokenStream, input: TokenStream) -> TokenStream {
     let item = syn::parse_macro_input!(input as syn::ItemStruct);
     let args = syn::parse_macro_input!(args as Args);
 
     let mut properties_calls = quote! {};
+    let mut debug_context = false;
     for arg in args.args {
+        if is_call_to(&arg, "debug_context") {
+            debug_context = true;
+            continue;
+        }
+
         properties_calls = quote! {
             #properties_calls.#arg
         }
     }
+    let window_constructor = if debug_context {
+        quote! { AppWindow::new_debug }
+    } else {
+        quote! { AppWindow::new }
+    };
 
     let struct_name = item.clone().ident;
     let output = quote! {
@@ -55,7 +73,7 @@ pub fn main_app(args: TokenStream, input: TokenStream) -> TokenStream {
             use glutin::monitor::*;
 
             let window_builder = WindowBuilder::new().with_title("Application").with_inner_size(LogicalSize::new(512, 512))#properties_calls;
-            let mut window = AppWindow::new(window_builder);
+            let mut window = #window_constructor(window_builder);
             window.run::<#struct_name>();
         }
     };
@@ -82,3 +100,436 @@ pub fn derive_ebo_object(input: TokenStream) -> TokenStream {
 pub fn derive_texture_object(input: TokenStream) -> TokenStream {
     easy_derive!(TextureObject, input)
 }
+
+/// Implements `Std140` for a struct with named fields: every field is placed
+/// at the next multiple of its base alignment and the struct is padded to a
+/// multiple of 16 bytes, as the std140 rules require.
+#[proc_macro_derive(Std140)]
+pub fn derive_std140(input: TokenStream) -> TokenStream {
+    let item = syn::parse_macro_input!(input as syn::ItemStruct);
+    let struct_name = &item.ident;
+
+    if !item.generics.params.is_empty() {
+        return syn::Error::new_spanned(&item.generics, "Std140 can not be derived for generic structs")
+            .to_compile_error()
+            .into();
+    }
+    let fields = match &item.fields {
+        syn::Fields::Named(fields) => &fields.named,
+        _ => {
+            return syn::Error::new_spanned(&item, "Std140 can only be derived for structs with named fields")
+                .to_compile_error()
+                .into();
+        }
+    };
+
+    let field_names: Vec<_> = fields.iter().map(|field| &field.ident).collect();
+    let field_types: Vec<_> = fields.iter().map(|field| &field.ty).collect();
+
+    let output = quote! {
+        impl Std140 for #struct_name {
+            const ALIGN: usize = {
+                let mut align = 16;
+                #(
+                    if <#field_types as Std140>::ALIGN > align {
+                        align = <#field_types as Std140>::ALIGN;
+                    }
+                )*
+                align
+            };
+            const SIZE: usize = {
+                let mut offset: usize = 0;
+                #(
+                    let field_align = <#field_types as Std140>::ALIGN;
+                    offset = offset.div_ceil(field_align) * field_align;
+                    offset += <#field_types as Std140>::SIZE;
+                )*
+                offset.div_ceil(Self::ALIGN) * Self::ALIGN
+            };
+
+            fn write_std140(&self, buffer: &mut [u8]) {
+                let mut offset: usize = 0;
+                #(
+                    let field_align = <#field_types as Std140>::ALIGN;
+                    offset = offset.div_ceil(field_align) * field_align;
+                    Std140::write_std140(
+                        &self.#field_names,
+                        &mut buffer[offset..offset + <#field_types as Std140>::SIZE],
+                    );
+                    offset += <#field_types as Std140>::SIZE;
+                )*
+                let _ = offset;
+            }
+        }
+    };
+
+    output.into()
+}
+
+/// `shader_files!("res/shaders")` evaluates to a `gfx::basic::ShaderFiles`
+/// for a directory relative to the invoking crate. With the crate's
+/// `embed-shaders` feature every file below the directory is embedded with
+/// `include_str!`, so cargo rebuilds the crate when one of them changes (new
+/// files are only picked up by the next build of the crate). Otherwise the
+/// files are read from disk, found at runtime with `ShaderFiles::locate`, and
+/// can still be edited while the program runs. The invoking crate has to
+/// declare the `embed-shaders` feature.
+#[proc_macro]
+pub fn shader_files(input: TokenStream) -> TokenStream {
+    let directory = syn::parse_macro_input!(input as syn::LitStr);
+
+    let manifest_dir = std::env::var("CARGO_MANIFEST_DIR").unwrap_or_else(|_| ".".to_string());
+    let root = std::path::Path::new(&manifest_dir).join(directory.value());
+
+    let mut files = Vec::new();
+    if let Err(error) = collect_files(&root, &root, &mut files) {
+        return syn::Error::new_spanned(
+            &directory,
+            format!("Could not read shader directory {}: {}", root.display(), error),
+        )
+        .to_compile_error()
+        .into();
+    }
+    files.sort();
+
+    let relative_paths = files.iter().map(|(relative, _)| relative);
+    let absolute_paths = files.iter().map(|(_, absolute)| absolute);
+
+    let output = quote! {
+        {
+            #[cfg(feature = "embed-shaders")]
+            let files = ::gfx::basic::ShaderFiles::embedded(
+                #directory,
+                &[#((#relative_paths, include_str!(#absolute_paths))),*],
+            );
+            #[cfg(not(feature = "embed-shaders"))]
+            let files = ::gfx::basic::ShaderFiles::locate(#directory);
+
+            files
+        }
+    };
+
+    output.into()
+}
+
+//  Collects `(path relative to root, absolute path)` of every file below
+//  `directory`, using `/` as separator for the relative paths.
+fn collect_files(
+    root: &std::path::Path,
+    directory: &std::path::Path,
+    files: &mut Vec<(String, String)>,
+) -> std::io::Result<()> {
+    for entry in std::fs::read_dir(directory)? {
+        let path = entry?.path();
+        if path.is_dir() {
+            collect_files(root, &path, files)?;
+        } else if let Ok(relative) = path.strip_prefix(root) {
+            let relative = relative
+                .components()
+                .map(|component| component.as_os_str().to_string_lossy())
+                .collect::<Vec<_>>()
+                .join("/");
+            files.push((relative, path.to_string_lossy().into_owned()));
+        }
+    }
+
+    Ok(())
+}
+
+/// Implements `Uniforms` for a struct with named fields. Every field is
+/// uploaded to the uniform with the same name; `#[uniform(name = "...")]`
+/// uses another name and `#[uniform(skip)]` leaves the field out.
+#[proc_macro_derive(Uniforms, attributes(uniform))]
+pub fn derive_uniforms(input: TokenStream) -> TokenStream {
+    let item = syn::parse_macro_input!(input as syn::ItemStruct);
+    let struct_name = &item.ident;
+
+    if !item.generics.params.is_empty() {
+        return syn::Error::new_spanned(&item.generics, "Uniforms can not be derived for generic structs")
+            .to_compile_error()
+            .into();
+    }
+    let fields = match &item.fields {
+        syn::Fields::Named(fields) => &fields.named,
+        _ => {
+            return syn::Error::new_spanned(&item, "Uniforms can only be derived for structs with named fields")
+                .to_compile_error()
+                .into();
+        }
+    };
+
+    let mut field_names = Vec::new();
+    let mut field_types = Vec::new();
+    let mut uniform_names = Vec::new();
+    for field in fields {
+        match parse_uniform_attributes(field) {
+            Ok(Some(uniform_name)) => {
+                field_names.push(&field.ident);
+                field_types.push(&field.ty);
+                uniform_names.push(uniform_name);
+            }
+            Ok(None) => {}
+            Err(error) => return error.to_compile_error().into(),
+        }
+    }
+    let indices = 0..field_names.len();
+
+    let output = quote! {
+        impl Uniforms for #struct_name {
+            fn locate(shader: &mut impl ShaderObject) -> Vec<i32> {
+                vec![#(<#field_types as ShaderUniform>::checked_location(shader, #uniform_names)),*]
+            }
+
+            fn upload(&self, locations: &[i32]) {
+                #(
+                    if locations[#indices] != -1 {
+                        ShaderUniform::upload(&self.#field_names, locations[#indices]);
+                    }
+                )*
+            }
+        }
+    };
+
+    output.into()
+}
+
+//  The uniform name of a field, `None` if it is skipped.
+fn parse_uniform_attributes(field: &syn::Field) -> Result<Option<String>, syn::Error> {
+    let mut name = field.ident.as_ref().map(|ident| ident.to_string());
+
+    for attribute in field.attrs.iter().filter(|attribute| attribute.path.is_ident("uniform")) {
+        let list = match attribute.parse_meta()? {
+            syn::Meta::List(list) => list,
+            meta => return Err(syn::Error::new_spanned(meta, "expected #[uniform(...)]")),
+        };
+
+        for nested in list.nested.iter() {
+            match nested {
+                syn::NestedMeta::Meta(syn::Meta::Path(path)) if path.is_ident("skip") => {
+                    return Ok(None);
+                }
+                syn::NestedMeta::Meta(syn::Meta::NameValue(name_value))
+                    if name_value.path.is_ident("name") =>
+                {
+                    match &name_value.lit {
+                        syn::Lit::Str(value) => name = Some(value.value()),
+                        lit => return Err(syn::Error::new_spanned(lit, "expected a string")),
+                    }
+                }
+                nested => {
+                    return Err(syn::Error::new_spanned(
+                        nested,
+                        "expected `name = \"...\"` or `skip`",
+                    ))
+                }
+            }
+        }
+    }
+
+    Ok(name)
+}
+
+/// Implements `Vertex` for a `#[repr(C)]` struct with named fields. Fields
+/// feed consecutive attribute locations starting at 0 (matrices one per
+/// column); `#[vertex(location = N)]` moves a field (and the ones after it)
+/// and `#[vertex(normalized)]` maps integer components to [0, 1] or [-1, 1]. `#[vertex(integer)]` feeds an
+/// `int`/`uint` attribute and `#[vertex(double)]` a `double`/`dvec` one.
+#[proc_macro_derive(Vertex, attributes(vertex))]
+pub fn derive_vertex(input: TokenStream) -> TokenStream {
+    let item = syn::parse_macro_input!(input as syn::ItemStruct);
+    let struct_name = &item.ident;
+
+    if !item.generics.params.is_empty() {
+        return syn::Error::new_spanned(&item.generics, "Vertex can not be derived for generic structs")
+            .to_compile_error()
+            .into();
+    }
+    if !is_repr_c(&item) {
+        return syn::Error::new_spanned(&item.ident, "Vertex can only be derived for #[repr(C)] structs")
+            .to_compile_error()
+            .into();
+    }
+    let fields = match &item.fields {
+        syn::Fields::Named(fields) => &fields.named,
+        _ => {
+            return syn::Error::new_spanned(&item, "Vertex can only be derived for structs with named fields")
+                .to_compile_error()
+                .into();
+        }
+    };
+
+    let mut field_names = Vec::new();
+    let mut field_types = Vec::new();
+    let mut locations = Vec::new();
+    let mut kinds = Vec::new();
+    for field in fields {
+        let (location, kind) = match parse_vertex_attributes(field) {
+            Ok(attributes) => attributes,
+            Err(error) => return error.to_compile_error().into(),
+        };
+
+        field_names.push(&field.ident);
+        field_types.push(&field.ty);
+        locations.push(match location {
+            Some(location) => quote!(#location),
+            None => quote!(next_location),
+        });
+        kinds.push(match kind {
+            VertexKind::Float { normalized } => {
+                quote!(AttributeKind::Float { normalized: #normalized })
+            }
+            VertexKind::Integer => quote!(AttributeKind::Integer),
+            VertexKind::Double => quote!(AttributeKind::Double),
+        });
+    }
+
+    //  Matrices take one location per column, so the next location is only
+    //  known once the field types are.
+    let output = quote! {
+        impl Vertex for #struct_name {
+            fn layout() -> VaoLayout {
+                let mut layout = VaoLayout::with_stride(std::mem::size_of::<Self>());
+                let mut next_location: u32 = 0;
+                #(
+                    let location: u32 = #locations;
+                    let locations = <#field_types as VertexAttribute>::LOCATIONS;
+                    let column_size = std::mem::size_of::<#field_types>() / locations as usize;
+                    for column in 0..locations {
+                        layout.push_element_with_offset(
+                            location + column,
+                            std::mem::offset_of!(#struct_name, #field_names)
+                                + column as usize * column_size,
+                            <#field_types as VertexAttribute>::COUNT,
+                            AttribType::from_gl(<#field_types as VertexAttribute>::GL_TYPE),
+                            #kinds,
+                        );
+                    }
+                    next_location = location + locations;
+                )*
+                let _ = next_location;
+                layout
+            }
+        }
+    };
+
+    output.into()
+}
+
+fn is_repr_c(item: &syn::ItemStruct) -> bool {
+    item.attrs
+        .iter()
+        .filter(|attribute| attribute.path.is_ident("repr"))
+        .filter_map(|attribute| match attribute.parse_meta() {
+            Ok(syn::Meta::List(list)) => Some(list),
+            _ => None,
+        })
+        .any(|list| {
+            list.nested.iter().any(|nested| {
+                matches!(nested, syn::NestedMeta::Meta(syn::Meta::Path(path)) if path.is_ident("C"))
+            })
+        })
+}
+
+//  Mirrors `gfx::basic::AttributeKind`.
+enum VertexKind {
+    Float { normalized: bool },
+    Integer,
+    Double,
+}
+
+//  The explicit location of a field and its kind.
+fn parse_vertex_attributes(field: &syn::Field) -> Result<(Option<u32>, VertexKind), syn::Error> {
+    let mut location = None;
+    let mut normalized = false;
+    let mut kind = None;
+
+    for attribute in field.attrs.iter().filter(|attribute| attribute.path.is_ident("vertex")) {
+        let list = match attribute.parse_meta()? {
+            syn::Meta::List(list) => list,
+            meta => return Err(syn::Error::new_spanned(meta, "expected #[vertex(...)]")),
+        };
+
+        for nested in list.nested.iter() {
+            match nested {
+                syn::NestedMeta::Meta(syn::Meta::Path(path)) if path.is_ident("normalized") => {
+                    normalized = true;
+                }
+                syn::NestedMeta::Meta(syn::Meta::Path(path)) if path.is_ident("integer") => {
+                    kind = Some(VertexKind::Integer);
+                }
+                syn::NestedMeta::Meta(syn::Meta::Path(path)) if path.is_ident("double") => {
+                    kind = Some(VertexKind::Double);
+                }
+                syn::NestedMeta::Meta(syn::Meta::NameValue(name_value))
+                    if name_value.path.is_ident("location") =>
+                {
+                    match &name_value.lit {
+                        syn::Lit::Int(value) => location = Some(value.base10_parse::<u32>()?),
+                        lit => return Err(syn::Error::new_spanned(lit, "expected an integer")),
+                    }
+                }
+                nested => {
+                    return Err(syn::Error::new_spanned(
+                        nested,
+                        "expected `location = N`, `normalized`, `integer` or `double`",
+                    ))
+                }
+            }
+        }
+    }
+
+    if normalized && kind.is_some() {
+        return Err(syn::Error::new_spanned(field, "only float attributes can be normalized"));
+    }
+    let kind = kind.unwrap_or(VertexKind::Float { normalized });
+
+    Ok((location, kind))
+}
+
+/// Implements `Pod` for a `#[repr(C)]` struct with named fields. Fails to
+/// compile if a field is not `Pod` or the struct has padding.
+#[proc_macro_derive(Pod)]
+pub fn derive_pod(input: TokenStream) -> TokenStream {
+    let item = syn::parse_macro_input!(input as syn::ItemStruct);
+    let struct_name = &item.ident;
+
+    if !item.generics.params.is_empty() {
+        return syn::Error::new_spanned(&item.generics, "Pod can not be derived for generic structs")
+            .to_compile_error()
+            .into();
+    }
+    if !is_repr_c(&item) {
+        return syn::Error::new_spanned(&item.ident, "Pod can only be derived for #[repr(C)] structs")
+            .to_compile_error()
+            .into();
+    }
+    let fields = match &item.fields {
+        syn::Fields::Named(fields) => &fields.named,
+        _ => {
+            return syn::Error::new_spanned(&item, "Pod can only be derived for structs with named fields")
+                .to_compile_error()
+                .into();
+        }
+    };
+
+    let field_types: Vec<_> = fields.iter().map(|field| &field.ty).collect();
+    let message = format!("{} has padding between or after its fields", struct_name);
+
+    let output = quote! {
+        const _: () = {
+            fn assert_pod<T: Pod>() {}
+            fn assert_fields() {
+                #(assert_pod::<#field_types>();)*
+            }
+
+            assert!(
+                std::mem::size_of::<#struct_name>() == 0 #(+ std::mem::size_of::<#field_types>())*,
+                #message
+            );
+        };
+
+        unsafe impl Pod for #struct_name {}
+    };
+
+    output.into()
+}
@@ -12,7 +12,16 @@ pub fn size_of_gl_type(gl_type: GLenum) -> Option<usize> {
         gl::BYTE => Some(mem::size_of::<GLbyte>()),
         gl::UNSIGNED_BYTE => Some(mem::size_of::<GLubyte>()),
         gl::SHORT => Some(mem::size_of::<GLshort>()),
+        gl::UNSIGNED_SHORT => Some(mem::size_of::<GLushort>()),
         gl::BOOL => Some(mem::size_of::<GLboolean>()),
+        gl::HALF_FLOAT => Some(mem::size_of::<GLhalf>()),
+        gl::DOUBLE => Some(mem::size_of::<GLdouble>()),
+        //  16.16 fixed point.
+        gl::FIXED => Some(mem::size_of::<GLint>()),
+        //  Packed types hold all their components in a single value.
+        gl::INT_2_10_10_10_REV
+        | gl::UNSIGNED_INT_2_10_10_10_REV
+        | gl::UNSIGNED_INT_10F_11F_11F_REV => Some(mem::size_of::<GLuint>()),
         _ => {
             warn!("Could not find the size of gl type {}", gl_type);
             None
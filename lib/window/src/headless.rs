@@ -0,0 +1,333 @@
+use gfx::GlContext;
+use gl::types::*;
+use glutin::{
+    dpi::PhysicalSize,
+    event_loop::{ControlFlow, EventLoop},
+    Context, ContextBuilder, CreationError, GlRequest, NotCurrent, PossiblyCurrent,
+};
+use image::RgbaImage;
+use log::info;
+use simple_logger::SimpleLogger;
+use std::ffi::c_void;
+use winit_input_helper::WinitInputHelper;
+
+use super::Application;
+#[cfg(any(
+    target_os = "linux",
+    target_os = "dragonfly",
+    target_os = "freebsd",
+    target_os = "netbsd",
+    target_os = "openbsd",
+))]
+use super::surfaceless::SurfacelessContext;
+
+/// Runs an `Application` without a visible window. Frames are rendered into
+/// an offscreen framebuffer and can be read back with `read_frame`.
+pub struct HeadlessWindow {
+    context: HeadlessContext,
+    input_helper: WinitInputHelper,
+    gl_context: GlContext,
+
+    width: u32,
+    height: u32,
+    framebuffer: GLuint,
+    color_buffer: GLuint,
+    depth_buffer: GLuint,
+}
+
+impl HeadlessWindow {
+    /// Creates an OSMesa context when available (Mesa's software rasterizer
+    /// needs neither a display nor a gpu), then tries Mesa's surfaceless EGL
+    /// platform, and falls back to a glutin surfaceless or pbuffer context,
+    /// which need a display server.
+    pub fn new(width: u32, height: u32) -> Result<HeadlessWindow, CreationError> {
+        //  Several headless windows may be created by the same process.
+        let _ = SimpleLogger::new().init();
+
+        info!("Creating headless context");
+        let context = build_context(PhysicalSize::new(width, height))?;
+
+        info!("Loading OpenGL");
+        gl::load_with(|s| context.get_proc_address(s));
+        info!("Successfully loaded OpenGL");
+        let gl_context = unsafe { GlContext::new() };
+
+        let mut framebuffer = 0;
+        let mut color_buffer = 0;
+        let mut depth_buffer = 0;
+        unsafe {
+            gl::GenFramebuffers(1, &mut framebuffer);
+            gl::BindFramebuffer(gl::FRAMEBUFFER, framebuffer);
+
+            gl::GenRenderbuffers(1, &mut color_buffer);
+            gl::BindRenderbuffer(gl::RENDERBUFFER, color_buffer);
+            gl::RenderbufferStorage(gl::RENDERBUFFER, gl::RGBA8, width as i32, height as i32);
+            gl::FramebufferRenderbuffer(
+                gl::FRAMEBUFFER,
+                gl::COLOR_ATTACHMENT0,
+                gl::RENDERBUFFER,
+                color_buffer,
+            );
+
+            gl::GenRenderbuffers(1, &mut depth_buffer);
+            gl::BindRenderbuffer(gl::RENDERBUFFER, depth_buffer);
+            gl::RenderbufferStorage(
+                gl::RENDERBUFFER,
+                gl::DEPTH24_STENCIL8,
+                width as i32,
+                height as i32,
+            );
+            gl::FramebufferRenderbuffer(
+                gl::FRAMEBUFFER,
+                gl::DEPTH_STENCIL_ATTACHMENT,
+                gl::RENDERBUFFER,
+                depth_buffer,
+            );
+
+            if gl::CheckFramebufferStatus(gl::FRAMEBUFFER) != gl::FRAMEBUFFER_COMPLETE {
+                gl::BindFramebuffer(gl::FRAMEBUFFER, 0);
+                gl::DeleteFramebuffers(1, &framebuffer);
+                gl::DeleteRenderbuffers(1, &color_buffer);
+                gl::DeleteRenderbuffers(1, &depth_buffer);
+                return Err(CreationError::OsError(
+                    "The offscreen framebuffer is incomplete".to_string(),
+                ));
+            }
+
+            gl::Viewport(0, 0, width as i32, height as i32);
+        }
+
+        info!("Headless context successfully created");
+
+        Ok(HeadlessWindow {
+            context,
+            input_helper: WinitInputHelper::new(),
+            gl_context,
+            width,
+            height,
+            framebuffer,
+            color_buffer,
+            depth_buffer,
+        })
+    }
+
+    /// Runs `T` for `frames` frames with a fixed delta of 1/60s and returns the
+    /// last rendered frame. Stops early if the application sets
+    /// `ControlFlow::Exit`.
+    pub fn run<T>(&mut self, frames: u32) -> RgbaImage
+    where
+        T: Application,
+    {
+        const DELTA: f64 = 1.0 / 60.0;
+
+        info!("Loading Init Function");
+        let mut application = T::init(&self.gl_context);
+        application.resize(self.width as i32, self.height as i32);
+
+        info!("Running Application for {} frames", frames);
+        let mut control_flow = ControlFlow::default();
+        for _ in 0..frames {
+            application.logic(&mut self.input_helper, &mut control_flow, DELTA);
+
+            unsafe { gl::BindFramebuffer(gl::FRAMEBUFFER, self.framebuffer) };
+            application.draw();
+            self.gl_context.collect_garbage();
+
+            if control_flow == ControlFlow::Exit {
+                break;
+            }
+        }
+
+        let frame = self.read_frame();
+
+        info!("Closing Application");
+        application.close();
+
+        frame
+    }
+
+    /// Reads the content of the offscreen framebuffer.
+    pub fn read_frame(&self) -> RgbaImage {
+        let mut pixels = vec![0u8; self.width as usize * self.height as usize * 4];
+
+        unsafe {
+            gl::Finish();
+            gl::BindFramebuffer(gl::READ_FRAMEBUFFER, self.framebuffer);
+            gl::PixelStorei(gl::PACK_ALIGNMENT, 1);
+            gl::ReadPixels(
+                0,
+                0,
+                self.width as i32,
+                self.height as i32,
+                gl::RGBA,
+                gl::UNSIGNED_BYTE,
+                pixels.as_mut_ptr() as *mut c_void,
+            );
+        }
+
+        //  OpenGL starts from the bottom row.
+        let image = RgbaImage::from_raw(self.width, self.height, pixels).unwrap();
+        image::imageops::flip_vertical(&image)
+    }
+
+    /// The glutin context, `None` on Mesa's surfaceless EGL platform.
+    pub fn context(&self) -> Option<&Context<PossiblyCurrent>> {
+        match &self.context {
+            HeadlessContext::Glutin { context, .. } => Some(context),
+            #[cfg(any(
+                target_os = "linux",
+                target_os = "dragonfly",
+                target_os = "freebsd",
+                target_os = "netbsd",
+                target_os = "openbsd",
+            ))]
+            HeadlessContext::Surfaceless(_) => None,
+        }
+    }
+
+    pub fn gl_context(&self) -> &GlContext {
+        &self.gl_context
+    }
+}
+
+impl Drop for HeadlessWindow {
+    fn drop(&mut self) {
+        unsafe {
+            gl::DeleteFramebuffers(1, &self.framebuffer);
+            gl::DeleteRenderbuffers(1, &self.color_buffer);
+            gl::DeleteRenderbuffers(1, &self.depth_buffer);
+        }
+    }
+}
+
+//  The context behind a `HeadlessWindow`, with the event loop it may need.
+enum HeadlessContext {
+    Glutin {
+        context: Context<PossiblyCurrent>,
+        _event_loop: Option<EventLoop<()>>,
+    },
+    #[cfg(any(
+        target_os = "linux",
+        target_os = "dragonfly",
+        target_os = "freebsd",
+        target_os = "netbsd",
+        target_os = "openbsd",
+    ))]
+    Surfaceless(SurfacelessContext),
+}
+
+impl HeadlessContext {
+    fn current(
+        context: Context<NotCurrent>,
+        event_loop: Option<EventLoop<()>>,
+    ) -> Result<HeadlessContext, CreationError> {
+        let context = unsafe { context.make_current() }.map_err(|(_, error)| {
+            CreationError::OsError(format!("Could not make the context current: {:?}", error))
+        })?;
+
+        Ok(HeadlessContext::Glutin {
+            context,
+            _event_loop: event_loop,
+        })
+    }
+
+    fn get_proc_address(&self, name: &str) -> *const c_void {
+        match self {
+            HeadlessContext::Glutin { context, .. } => context.get_proc_address(name),
+            #[cfg(any(
+                target_os = "linux",
+                target_os = "dragonfly",
+                target_os = "freebsd",
+                target_os = "netbsd",
+                target_os = "openbsd",
+            ))]
+            HeadlessContext::Surfaceless(context) => context.get_proc_address(name),
+        }
+    }
+}
+
+fn context_builder() -> ContextBuilder<'static, NotCurrent> {
+    ContextBuilder::new().with_gl(GlRequest::Specific(glutin::Api::OpenGl, (3, 3)))
+}
+
+#[cfg(any(
+    target_os = "linux",
+    target_os = "dragonfly",
+    target_os = "freebsd",
+    target_os = "netbsd",
+    target_os = "openbsd",
+))]
+fn build_context(size: PhysicalSize<u32>) -> Result<HeadlessContext, CreationError> {
+    use glutin::platform::unix::{EventLoopExtUnix, HeadlessContextExt};
+
+    let error = match context_builder().build_osmesa(size) {
+        Ok(context) => return HeadlessContext::current(context, None),
+        Err(error) => error,
+    };
+    info!("OSMesa is not available ({}), trying surfaceless EGL", error);
+
+    let error = match SurfacelessContext::new(3, 3) {
+        Ok(context) => return Ok(HeadlessContext::Surfaceless(context)),
+        Err(error) => error,
+    };
+    info!("Surfaceless EGL is not available ({}), trying a display", error);
+
+    //  Tests run outside of the main thread.
+    let event_loop = EventLoop::new_any_thread();
+    let context = context_builder()
+        .build_surfaceless(&event_loop)
+        .or_else(|_| context_builder().build_headless(&event_loop, size))?;
+
+    HeadlessContext::current(context, Some(event_loop))
+}
+
+#[cfg(not(any(
+    target_os = "linux",
+    target_os = "dragonfly",
+    target_os = "freebsd",
+    target_os = "netbsd",
+    target_os = "openbsd",
+)))]
+fn build_context(size: PhysicalSize<u32>) -> Result<HeadlessContext, CreationError> {
+    let event_loop = EventLoop::new();
+    let context = context_builder().build_headless(&event_loop, size)?;
+
+    HeadlessContext::current(context, Some(event_loop))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct ClearApp;
+
+    impl Application for ClearApp {
+        fn init(_context: &GlContext) -> ClearApp {
+            ClearApp
+        }
+
+        fn draw(&mut self) {
+            unsafe {
+                gl::ClearColor(1.0, 0.0, 0.0, 1.0);
+                gl::Clear(gl::COLOR_BUFFER_BIT);
+            }
+        }
+
+        fn logic(&mut self, _: &mut WinitInputHelper, _: &mut ControlFlow, _: f64) {}
+
+        fn resize(&mut self, _width: i32, _height: i32) {}
+
+        fn close(&mut self) {}
+    }
+
+    //  Needs OSMesa or an EGL display, run with `cargo test -- --ignored`.
+    #[test]
+    #[ignore]
+    fn run_returns_a_frame_of_the_window_size() {
+        let mut window = HeadlessWindow::new(64, 32).expect("no headless context available");
+        let frame = window.run::<ClearApp>(3);
+
+        assert_eq!(frame.dimensions(), (64, 32));
+        assert_eq!(frame.get_pixel(10, 10).0, [255, 0, 0, 255]);
+    }
+}
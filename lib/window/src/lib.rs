@@ -1,6 +1,16 @@
 mod application;
+mod headless;
+#[cfg(any(
+    target_os = "linux",
+    target_os = "dragonfly",
+    target_os = "freebsd",
+    target_os = "netbsd",
+    target_os = "openbsd",
+))]
+mod surfaceless;
 mod window;
 
 pub use application::Application;
+pub use headless::HeadlessWindow;
 pub use window::Window;
 pub use macros::main_app;
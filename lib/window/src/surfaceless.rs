@@ -0,0 +1,88 @@
+use glutin_egl_sys::egl::{self, types::*, Egl};
+use libloading::Library;
+use std::{
+    ffi::{c_void, CString},
+    ptr,
+};
+
+//  EGL_MESA_platform_surfaceless, not part of the generated bindings.
+const PLATFORM_SURFACELESS_MESA: EGLenum = 0x31DD;
+//  EGL_KHR_no_config_context.
+const NO_CONFIG: EGLConfig = ptr::null();
+
+/// A core profile context on Mesa's surfaceless EGL platform. It needs neither
+/// a display server nor a window, which glutin's headless contexts do, so the
+/// offscreen framebuffer is all there is to render to.
+pub(crate) struct SurfacelessContext {
+    egl: Egl,
+    display: EGLDisplay,
+    context: EGLContext,
+    //  Keeps the function pointers of `egl` valid.
+    _library: Library,
+}
+
+impl SurfacelessContext {
+    pub(crate) fn new(major: u8, minor: u8) -> Result<SurfacelessContext, String> {
+        let library = unsafe { Library::new("libEGL.so.1") }.map_err(|error| error.to_string())?;
+        let egl = Egl::load_with(|name| {
+            let symbol = CString::new(name).unwrap();
+            unsafe { library.get::<*const c_void>(symbol.as_bytes_with_nul()) }
+                .map_or(ptr::null(), |function| *function)
+        });
+        if !egl.GetPlatformDisplay.is_loaded() {
+            return Err("eglGetPlatformDisplay is not available".to_string());
+        }
+
+        unsafe {
+            let display = egl.GetPlatformDisplay(
+                PLATFORM_SURFACELESS_MESA,
+                egl::DEFAULT_DISPLAY as *mut c_void,
+                ptr::null(),
+            );
+            if display == egl::NO_DISPLAY || egl.Initialize(display, ptr::null_mut(), ptr::null_mut()) == 0 {
+                return Err(format!("no surfaceless EGL display (error {:#x})", egl.GetError()));
+            }
+
+            let attributes = [
+                egl::CONTEXT_MAJOR_VERSION as EGLint,
+                major as EGLint,
+                egl::CONTEXT_MINOR_VERSION as EGLint,
+                minor as EGLint,
+                egl::CONTEXT_OPENGL_PROFILE_MASK as EGLint,
+                egl::CONTEXT_OPENGL_CORE_PROFILE_BIT as EGLint,
+                egl::NONE as EGLint,
+            ];
+            egl.BindAPI(egl::OPENGL_API);
+            let context = egl.CreateContext(display, NO_CONFIG, egl::NO_CONTEXT, attributes.as_ptr());
+            if context == egl::NO_CONTEXT
+                || egl.MakeCurrent(display, egl::NO_SURFACE, egl::NO_SURFACE, context) == 0
+            {
+                let error = egl.GetError();
+                egl.Terminate(display);
+                return Err(format!("could not create a surfaceless context (error {:#x})", error));
+            }
+
+            Ok(SurfacelessContext {
+                egl,
+                display,
+                context,
+                _library: library,
+            })
+        }
+    }
+
+    pub(crate) fn get_proc_address(&self, name: &str) -> *const c_void {
+        let name = CString::new(name).unwrap();
+        unsafe { self.egl.GetProcAddress(name.as_ptr()) as *const c_void }
+    }
+}
+
+impl Drop for SurfacelessContext {
+    fn drop(&mut self) {
+        unsafe {
+            self.egl.MakeCurrent(self.display, egl::NO_SURFACE, egl::NO_SURFACE, egl::NO_CONTEXT);
+            self.egl.DestroyContext(self.display, self.context);
+            self.egl.Terminate(self.display);
+        }
+    }
+}
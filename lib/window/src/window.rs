@@ -1,3 +1,4 @@
+use gfx::GlContext;
 use glutin::{
     dpi::PhysicalPosition,
     event::{Event, WindowEvent},
@@ -17,10 +18,21 @@ pub struct Window {
     windowed_context: glutin::ContextWrapper<glutin::PossiblyCurrent, glutin::window::Window>,
     event_loop: EventLoop<()>,
     input_helper: WinitInputHelper,
+    context: GlContext,
 }
 
 impl Window {
     pub fn new(window_builder: WindowBuilder) -> Window {
+        Window::build(window_builder, false)
+    }
+
+    /// Like `Window::new`, but requests a debug context so that the driver
+    /// reports everything through KHR_debug.
+    pub fn new_debug(window_builder: WindowBuilder) -> Window {
+        Window::build(window_builder, true)
+    }
+
+    fn build(window_builder: WindowBuilder, debug_context: bool) -> Window {
         SimpleLogger::new().init().unwrap();
         info!("Started logger");
 
@@ -33,6 +45,7 @@ impl Window {
         info!("Creating windowed context");
         let windowed_context = ContextBuilder::new()
             .with_gl(GlRequest::Specific(glutin::Api::OpenGl, (3, 3)))
+            .with_gl_debug_flag(debug_context)
             .build_windowed(window_builder, &event_loop)
             .unwrap();
         let windowed_context = unsafe { windowed_context.make_current().unwrap() };
@@ -43,11 +56,13 @@ impl Window {
         info!("Loading OpenGL");
         gl::load_with(|s| windowed_context.get_proc_address(s) as *const _);
         info!("Successfully loaded OpenGL");
+        let context = unsafe { GlContext::new() };
 
         Window {
             windowed_context,
             event_loop,
             input_helper,
+            context,
         }
     }
 
@@ -60,9 +75,10 @@ impl Window {
 
         let windowed_context = &mut self.windowed_context;
         let input_helper = &mut self.input_helper;
+        let context = &self.context;
 
         info!("Loading Init Function");
-        let mut application = T::init();
+        let mut application = T::init(context);
 
         info!("Starting Application Main Loop");
         self.event_loop
@@ -82,6 +98,7 @@ impl Window {
 
                 application.draw();
                 windowed_context.swap_buffers().unwrap();
+                context.collect_garbage();
 
                 match event {
                     Event::LoopDestroyed => {
@@ -4,9 +4,7 @@ layout (location = 1) in vec2 aUV; // the color variable has attribute position
   
 out vec2 ourUV; // output a color to the fragment shader
 
-uniform mat4 model;
-uniform mat4 view;
-uniform mat4 proj;
+#include "include/matrices.glsl"
 
 void main()
 {
@@ -0,0 +1,7 @@
+layout (std140) uniform Camera
+{
+    mat4 view;
+    mat4 proj;
+};
+
+uniform mat4 model;
@@ -4,12 +4,10 @@ layout (location = 1) in float aColor; // the color variable has attribute posit
   
 out float ourColor; // output a color to the fragment shader
 
-uniform mat4 model;
-uniform mat4 view;
-uniform mat4 projection;
+#include "include/matrices.glsl"
 
 void main()
 {
-    gl_Position =  projection * view * model * vec4(aPos, 1.0);
+    gl_Position =  proj * view * model * vec4(aPos, 1.0);
     ourColor = aColor; // set ourColor to the input color we got from the vertex data
 }
\ No newline at end of file
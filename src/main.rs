@@ -1,8 +1,11 @@
 use cgmath::{vec3, Matrix4};
 use gfx::{
-    basic::{Camera, Texture, TextureBuilder, VaoLayout},
+    basic::{
+        enable_debug_output, Camera, CameraUniforms, DebugFilter, Texture, TextureBuilder,
+        UniformBuffer, Vao,
+    },
     prelude::*,
-    Mesh, MeshBuilder, Pipeline,
+    shader_files, DepthFunc, GlContext, Mesh, MeshBuilder, Pipeline, PolygonMode,
 };
 use glutin::{event::VirtualKeyCode, event_loop::ControlFlow};
 use logic::transform::{Movable, Rotable};
@@ -11,7 +14,7 @@ use window::{main_app, Application};
 use winit_input_helper::WinitInputHelper;
 
 #[allow(dead_code)]
-#[main_app(with_title("OpenGL"), with_inner_size(LogicalSize::new(800, 600)))]
+#[main_app(debug_context(), with_title("OpenGL"), with_inner_size(LogicalSize::new(800, 600)))]
 struct App {
     pipeline: Pipeline,
     mesh: Mesh,
@@ -22,60 +25,93 @@ struct App {
     mode: bool,
 
     camera: Camera,
+    camera_buffer: UniformBuffer<CameraUniforms>,
+    uniforms: BasicUniforms,
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Pod, Vertex)]
+struct BasicVertex {
+    position: [f32; 3],
+    tex_coords: [f32; 2],
+}
+
+fn vertex(position: [f32; 3], tex_coords: [f32; 2]) -> BasicVertex {
+    BasicVertex {
+        position,
+        tex_coords,
+    }
+}
+
+#[derive(Uniforms)]
+struct BasicUniforms {
+    model: Matrix4<f32>,
+    #[uniform(name = "mixValue")]
+    mix_value: f32,
 }
 
 impl Application for App {
-    fn init() -> App {
+    fn init(context: &GlContext) -> App {
+        enable_debug_output(DebugFilter::default());
+
         gl_call!(gl::Enable(gl::DEPTH_TEST));
         gl_call!(gl::Enable(gl::BLEND));
 
-        let mut pipeline = Pipeline::new(
-            Path::new("res/shaders/basic.vs"),
-            Path::new("res/shaders/basic.fs"),
-        );
-        pipeline.states.depth_test = Some(gl::LESS);
+        let shaders = shader_files!("res/shaders");
+        let shader = shaders
+            .shader(context, Path::new("basic.vs"), Path::new("basic.fs"))
+            .unwrap_or_else(|error| panic!("{}", error));
+        let mut pipeline = Pipeline::from_existing(shader, Vao::new(context));
+        pipeline.states.depth_test = Some(DepthFunc::Less);
+        pipeline.label("basic");
+        pipeline.watch();
 
         pipeline.bind();
 
-        let mut meshbuilder: MeshBuilder<[f32; 5]> = MeshBuilder::new();
+        let mut meshbuilder: MeshBuilder<BasicVertex> = MeshBuilder::new();
         meshbuilder.push_quad(&[
-            [-0.5, -0.5, -0.5, 0.0, 0.0],
-            [ 0.5, -0.5, -0.5, 1.0, 0.0],
-            [ 0.5,  0.5, -0.5, 1.0, 1.0],
-            [-0.5,  0.5, -0.5, 0.0, 1.0],
+            vertex([-0.5, -0.5, -0.5], [0.0, 0.0]),
+            vertex([ 0.5, -0.5, -0.5], [1.0, 0.0]),
+            vertex([ 0.5,  0.5, -0.5], [1.0, 1.0]),
+            vertex([-0.5,  0.5, -0.5], [0.0, 1.0]),
         ]);
         meshbuilder.push_quad(&[
-            [-0.5, -0.5, 0.5, 0.0, 0.0],
-            [ 0.5, -0.5, 0.5, 1.0, 0.0],
-            [ 0.5,  0.5, 0.5, 1.0, 1.0],
-            [-0.5,  0.5, 0.5, 0.0, 1.0],
+            vertex([-0.5, -0.5, 0.5], [0.0, 0.0]),
+            vertex([ 0.5, -0.5, 0.5], [1.0, 0.0]),
+            vertex([ 0.5,  0.5, 0.5], [1.0, 1.0]),
+            vertex([-0.5,  0.5, 0.5], [0.0, 1.0]),
         ]);
-        let mesh = meshbuilder.build();
+        let mesh = meshbuilder.build(context);
 
-        let mut vao_layout = VaoLayout::new();
-        vao_layout.push_element(3, gl::FLOAT, false);
-        vao_layout.push_element(2, gl::FLOAT, false);
-        vao_layout.apply_layout(pipeline.vao());
+        pipeline
+            .apply_layout(&BasicVertex::layout())
+            .unwrap_or_else(|error| panic!("{}", error));
 
         let texture1 =
-            TextureBuilder::from_file(&Path::new("res/textures/container.jpg"), false, false)
-                .build();
+            TextureBuilder::from_file(Path::new("res/textures/container.jpg"), false, false)
+                .build(context);
+        texture1.label("container");
         texture1.bind();
         pipeline.set_uniform("texture1", &texture1);
 
-        let texture2 = TextureBuilder::from_file(&Path::new("res/textures/wall.jpg"), false, true)
+        let texture2 = TextureBuilder::from_file(Path::new("res/textures/wall.jpg"), false, true)
             .active_texture_number(gl::TEXTURE1)
-            .build();
+            .build(context);
+        texture2.label("wall");
         texture2.bind();
         pipeline.set_uniform("texture2", &texture2);
 
-        let model_mat: Matrix4<f32> = Matrix4::from_translation(vec3(0.0, 0.0, -2.0));
-        pipeline.set_uniform("model", model_mat);
-
         let mut camera = Camera::new(800, 600, 100.0);
-        camera.bind_to_shader(&mut pipeline);
+        let camera_buffer = UniformBuffer::new(context, &camera.uniforms());
+        camera_buffer.label("camera");
+        camera_buffer.bind_base(0);
+        pipeline.bind_uniform_block("Camera", 0);
 
-        pipeline.set_uniform("mixValue", 0.5);
+        let uniforms = BasicUniforms {
+            model: Matrix4::from_translation(vec3(0.0, 0.0, -2.0)),
+            mix_value: 0.5,
+        };
+        uniforms.apply(&mut pipeline);
 
         App {
             pipeline,
@@ -85,6 +121,8 @@ impl Application for App {
             color: 0.0,
             mode: false,
             camera,
+            camera_buffer,
+            uniforms,
         }
     }
 
@@ -93,20 +131,24 @@ impl Application for App {
 
         gl_call!(gl::ClearColor(self.color, self.color, self.color, 1.0));
         gl_call!(gl::Clear(gl::COLOR_BUFFER_BIT | gl::DEPTH_BUFFER_BIT));
-        self.pipeline
-            .set_uniform("mixValue", (90.0 * self.color).to_radians().sin());
+        self.uniforms.mix_value = (90.0 * self.color).to_radians().sin();
+        self.uniforms.apply(&mut self.pipeline);
 
         self.mesh.draw();
     }
 
     fn logic(&mut self, input: &mut WinitInputHelper, control_flow: &mut ControlFlow, delta: f64) {
-        check_camera_inputs(&mut self.camera, &mut self.pipeline, input, delta);
+        if let Err(error) = self.pipeline.reload_if_changed() {
+            log::error!("{}", error);
+        }
+
+        check_camera_inputs(&mut self.camera, &mut self.camera_buffer, input, delta);
 
         if input.key_held(VirtualKeyCode::Z) {
-            self.pipeline.states.polygon_mode = gl::LINE;
+            self.pipeline.states.polygon_mode = PolygonMode::Line;
             self.pipeline.update_states();
         } else {
-            self.pipeline.states.polygon_mode = gl::FILL;
+            self.pipeline.states.polygon_mode = PolygonMode::Fill;
             self.pipeline.update_states();
         }
 
@@ -125,7 +167,7 @@ impl Application for App {
     }
 
     fn resize(&mut self, width: i32, height: i32) {
-        unsafe { gl::Viewport(0, 0, width as i32, height as i32) }
+        unsafe { gl::Viewport(0, 0, width, height) }
     }
 
     fn close(&mut self) {}
@@ -133,30 +175,30 @@ impl Application for App {
 
 fn check_camera_inputs(
     camera: &mut Camera,
-    pipeline: &mut Pipeline,
+    camera_buffer: &mut UniformBuffer<CameraUniforms>,
     input: &mut WinitInputHelper,
     delta: f64,
 ) {
     if input.key_held(VirtualKeyCode::W) {
         camera.move_forw(3.0 * delta as f32);
-        camera.bind_to_shader(pipeline);
+        camera.bind_to_buffer(camera_buffer);
     } else if input.key_held(VirtualKeyCode::S) {
         camera.move_back(3.0 * delta as f32);
-        camera.bind_to_shader(pipeline);
+        camera.bind_to_buffer(camera_buffer);
     }
     if input.key_held(VirtualKeyCode::D) {
         camera.move_right(3.0 * delta as f32);
-        camera.bind_to_shader(pipeline);
+        camera.bind_to_buffer(camera_buffer);
     } else if input.key_held(VirtualKeyCode::A) {
         camera.move_left(3.0 * delta as f32);
-        camera.bind_to_shader(pipeline);
+        camera.bind_to_buffer(camera_buffer);
     }
     if input.key_held(VirtualKeyCode::Space) {
         camera.simple_move(vec3(0.0, 1.0, 0.0), 3.0 * delta as f32);
-        camera.bind_to_shader(pipeline);
+        camera.bind_to_buffer(camera_buffer);
     } else if input.key_held(VirtualKeyCode::LShift) {
         camera.simple_move(vec3(0.0, -1.0, 0.0), 3.0 * delta as f32);
-        camera.bind_to_shader(pipeline);
+        camera.bind_to_buffer(camera_buffer);
     }
 
     if let Some((pos_x, pos_y)) = input.mouse() {
@@ -165,6 +207,28 @@ fn check_camera_inputs(
         camera.rotate(vec3(-1.0, 0.0, 0.0), pos_x * SENSIBILITY);
         camera.rotate(vec3(0.0, 1.0, 0.0), pos_y * SENSIBILITY);
 
-        camera.bind_to_shader(pipeline);
+        camera.bind_to_buffer(camera_buffer);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use gfx::golden::{assert_golden, GoldenTolerance};
+    use window::HeadlessWindow;
+
+    //  Needs OSMesa, surfaceless EGL or a display, run with
+    //  `cargo test -- --ignored`. Set UPDATE_GOLDEN=1 to rewrite the reference.
+    #[test]
+    #[ignore]
+    fn demo_scene_matches_the_reference() {
+        let mut window = HeadlessWindow::new(800, 600).expect("no headless context available");
+        let frame = window.run::<App>(30);
+
+        let tolerance = GoldenTolerance {
+            max_channel_difference: 4,
+            max_differing_pixels: 100,
+        };
+        assert_golden(&frame, Path::new("res/golden/demo.png"), tolerance);
     }
 }